@@ -1,203 +1,236 @@
+use crate::brain::GhostBrains;
 use crate::controls;
-use crate::game_logic;
-use crate::models::Ghost;
 use crate::components::game_board::GameBoard;
+use crate::components::maze_editor::MazeEditor;
 use crate::components::scoreboard::Scoreboard;
-use crate::constants::maze::INITIAL_MAZE;
-use crate::models::{Direction, Position};
+use crate::constants::game::{ticks_from_ms, EXTRA_LIFE_FLASH_MS, TICK_MS};
+use crate::engine::{GameEvent, GamePhase, GameState, Input, Replay, ReplayPlayer};
+use crate::maze::{Maze, MazePack, MazeTheme, MAZE_PACKS};
+use crate::models::Direction;
+use crate::pathfinding::GhostAi;
+use crate::query;
+use std::rc::Rc;
 use yew::prelude::*;
-use yew_hooks::use_counter;
-use gloo::timers::callback::{Interval, Timeout};
+use gloo::timers::callback::Interval;
+
+pub enum GameAction {
+    Start,
+    Restart(u64),
+    LoadReplay(Replay),
+    Tick(Input),
+    Pause,
+    TogglePause,
+    OpenEditor,
+    CloseEditor,
+    PlayMaze(Maze),
+    SelectPack(usize),
+    ToggleGhostAi,
+}
+
+/// Whether ticks are driven by the keyboard (and recorded) or by a replay.
+#[derive(Clone, PartialEq)]
+pub enum PlaybackMode {
+    Live(Replay),
+    Replaying(ReplayPlayer),
+}
+
+#[derive(Clone, PartialEq)]
+pub struct GameSession {
+    pub game: GameState,
+    pub mode: PlaybackMode,
+    /// Ticks left on the scoreboard's extra-life animation.
+    pub life_gained_ticks: u32,
+    /// The maze editor is open; the game is hidden and does not tick.
+    pub editing: bool,
+    /// Index into `MAZE_PACKS`, or `None` for a maze from the editor.
+    pub maze_pack: Option<usize>,
+}
+
+impl GameSession {
+    fn new(seed: u64) -> Self {
+        let mut game = GameState::new(seed);
+        game.brains = choose_brains();
+        Self {
+            game,
+            mode: PlaybackMode::Live(Replay::new(seed)),
+            life_gained_ticks: 0,
+            editing: false,
+            maze_pack: Some(0),
+        }
+    }
+
+    fn recording(&self) -> Option<Replay> {
+        match &self.mode {
+            PlaybackMode::Live(replay) if self.game.is_started() => Some(replay.clone()),
+            PlaybackMode::Live(_) => None,
+            PlaybackMode::Replaying(player) => Some(player.replay().clone()),
+        }
+    }
+
+    /// Colours for the maze being played.
+    fn theme(&self) -> MazeTheme {
+        self.maze_pack
+            .map(|index| MAZE_PACKS[index].maze_for_level(self.game.level).theme)
+            .unwrap_or_default()
+    }
+}
+
+/// Seed for a new game: the `?seed=` query parameter when present so a run can
+/// be reproduced, otherwise a fresh random one.
+fn choose_seed() -> u64 {
+    query::seed_from_url().unwrap_or_else(rand::random)
+}
+
+/// Ghost brains named by the `?ghosts=` query parameter, so personalities can
+/// be tried out in other ghosts' slots.
+fn choose_brains() -> GhostBrains {
+    GhostBrains::from_names(&query::ghost_brains_from_url())
+}
+
+impl Reducible for GameSession {
+    type Action = GameAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut session = (*self).clone();
+        match action {
+            GameAction::Start => {
+                session.game.start();
+                session.mode = PlaybackMode::Live(Replay::for_game(&session.game));
+            }
+            GameAction::Restart(seed) => {
+                session.game.restart(seed);
+                session.mode = PlaybackMode::Live(Replay::for_game(&session.game));
+            }
+            GameAction::LoadReplay(replay) => {
+                session.game = replay.initial_state();
+                session.maze_pack = MazePack::find(session.game.layouts());
+                session.mode = PlaybackMode::Replaying(ReplayPlayer::new(replay));
+            }
+            GameAction::Tick(_) if session.editing || session.game.is_paused() => {}
+            GameAction::Tick(input) => {
+                let events = match &mut session.mode {
+                    PlaybackMode::Live(recording) => {
+                        if session.game.is_started() && !session.game.is_over() {
+                            recording.record(input);
+                        }
+                        session.game.step(input)
+                    }
+                    PlaybackMode::Replaying(player) => player
+                        .next_input()
+                        .map(|input| session.game.step(input))
+                        .unwrap_or_default(),
+                };
+                session.life_gained_ticks = if events.contains(&GameEvent::ExtraLife) {
+                    ticks_from_ms(EXTRA_LIFE_FLASH_MS)
+                } else {
+                    session.life_gained_ticks.saturating_sub(1)
+                };
+            }
+            GameAction::Pause => session.game.pause(),
+            GameAction::TogglePause if session.game.is_paused() => session.game.resume(),
+            GameAction::TogglePause => session.game.pause(),
+            GameAction::OpenEditor => session.editing = true,
+            GameAction::CloseEditor => session.editing = false,
+            GameAction::PlayMaze(maze) => {
+                let ghost_ai = session.game.ghost_ai;
+                session.game = GameState::from_maze(maze, choose_seed());
+                session.game.ghost_ai = ghost_ai;
+                session.game.brains = choose_brains();
+                session.game.start();
+                session.mode = PlaybackMode::Live(Replay::for_game(&session.game));
+                session.life_gained_ticks = 0;
+                session.editing = false;
+                session.maze_pack = None;
+            }
+            GameAction::SelectPack(_) if session.game.is_started() => {}
+            GameAction::SelectPack(index) => {
+                let (seed, ghost_ai) = (session.game.seed(), session.game.ghost_ai);
+                session.game = GameState::from_mazes(MAZE_PACKS[index].layouts(), seed);
+                session.game.ghost_ai = ghost_ai;
+                session.game.brains = choose_brains();
+                session.mode = PlaybackMode::Live(Replay::for_game(&session.game));
+                session.maze_pack = Some(index);
+            }
+            GameAction::ToggleGhostAi if session.game.is_started() => {}
+            GameAction::ToggleGhostAi => {
+                session.game.ghost_ai = match session.game.ghost_ai {
+                    GhostAi::Greedy => GhostAi::ShortestPath,
+                    GhostAi::ShortestPath => GhostAi::Greedy,
+                };
+            }
+        }
+        Rc::new(session)
+    }
+}
 
 #[function_component]
 pub fn App() -> Html {
-    let maze = use_state(|| {
-        INITIAL_MAZE
-            .iter()
-            .map(|row| row.to_vec())
-            .collect::<Vec<_>>()
-    });
-    let pacman_pos = use_state(|| Position { x: 7, y: 6 });
-    let score = use_state(|| 0);
-    let is_dying = use_state(|| false);
-    let current_direction = use_state(|| Direction::None);
-    let game_over = use_state(|| false);
-    let move_counter = use_state(|| 0);
-    let is_invincible = use_state(|| false);
-    let lives = use_state(|| 3);
-    let restart_timer = use_state(|| false);
-    let initial_ghost_positions = use_state(|| Ghost::initialize_ghosts(&maze));
-    let ghosts = use_state(|| (*initial_ghost_positions).clone());
-    let invincibility = use_counter(0);
-    let game_won: UseStateHandle<bool> = use_state(|| false);
-    let game_started = use_state(|| false);
+    let session = use_reducer(|| GameSession::new(choose_seed()));
+    let pending_direction = use_mut_ref(|| None::<Direction>);
 
     let start_game = {
-        let game_started = game_started.clone();
-        Callback::from(move |_: MouseEvent| {  
-            game_started.set(true);
+        let session = session.dispatcher();
+        Callback::from(move |_: MouseEvent| {
+            session.dispatch(GameAction::Start);
         })
     };
 
-    let reset_positions = {
-        let pacman_pos = pacman_pos.clone();
-        let ghosts = ghosts.clone();
-        let initial_ghost_positions = initial_ghost_positions.clone();
-        let is_dying = is_dying.clone();
-        let current_direction = current_direction.clone();
-        let restart_timer = restart_timer.clone();
-        let lives = lives.clone();
-        let game_over = game_over.clone();
-        let game_started = game_started.clone();
-
-
-        move || {
-            if *lives > 1 {
-                restart_timer.set(true);
-                let timer_handle = Timeout::new(3000, move || {
-                    pacman_pos.set(Position { x: 7, y: 6 });
-                    ghosts.set((*initial_ghost_positions).clone());
-                    is_dying.set(false);
-                    current_direction.set(Direction::None);
-                    restart_timer.set(false);
-                    game_started.set(true);
-                });
-                timer_handle.forget();
-            } else {
-                game_over.set(true);
-            }
-        }
+    let restart_game = {
+        let session = session.dispatcher();
+        Callback::from(move |_: MouseEvent| {
+            session.dispatch(GameAction::Restart(choose_seed()));
+        })
     };
 
-    let restart_game = {
-        let maze = maze.clone();
-        let pacman_pos = pacman_pos.clone();
-        let score = score.clone();
-        let is_dying = is_dying.clone();
-        let current_direction = current_direction.clone();
-        let game_over = game_over.clone();
-        let move_counter = move_counter.clone();
-        let lives = lives.clone();
-        let ghosts = ghosts.clone();
-        let is_invincible = is_invincible.clone();
-        let restart_timer = restart_timer.clone();
-        let game_won: UseStateHandle<bool> = game_won.clone();
-        let game_started = game_started.clone();
+    let load_replay = {
+        let session = session.dispatcher();
+        Callback::from(move |replay: Replay| {
+            session.dispatch(GameAction::LoadReplay(replay));
+        })
+    };
 
+    let open_editor = {
+        let session = session.dispatcher();
         Callback::from(move |_: MouseEvent| {
-            maze.set(
-                INITIAL_MAZE
-                    .iter()
-                    .map(|row| row.to_vec())
-                    .collect::<Vec<_>>(),
-            );
-            game_started.set(true);
-            pacman_pos.set(Position { x: 7, y: 6 });
-            score.set(0);
-            is_dying.set(false);
-            current_direction.set(Direction::None);
-            game_over.set(false);
-            move_counter.set(0);
-            lives.set(3);
-            is_invincible.set(false);
-            restart_timer.set(false);
-            let initial_ghosts = Ghost::initialize_ghosts(&maze);
-            ghosts.set(initial_ghosts);
-            game_won.set(false);
+            session.dispatch(GameAction::OpenEditor);
         })
     };
 
-    {
-        let (
-            pacman_pos,
-            current_direction,
-            maze,
-            score,
-            game_over,
-            ghosts,
-            is_dying,
-            move_counter,
-            lives,
-            restart_timer,
-            invincibility,
-            game_won,
-            game_started
-        ) = (
-            pacman_pos.clone(),
-            current_direction.clone(),
-            maze.clone(),
-            score.clone(),
-            game_over.clone(),
-            ghosts.clone(),
-            is_dying.clone(),
-            move_counter.clone(),
-            lives.clone(),
-            restart_timer.clone(),
-            invincibility.clone(),
-            game_won.clone(),
-            game_started.clone(),
-        );
-
-        use_effect(move || {
-            let interval = Interval::new(150, move || {
-                if !*game_started || *game_over || *is_dying || *restart_timer {
-                    return;
-                }
-
-                let new_counter = *move_counter + 1;
-                move_counter.set(new_counter);
-
-                if game_logic::check_ghost_collision(
-                    &pacman_pos,
-                    &ghosts,
-                    is_dying.clone(),
-                    lives.clone(),
-                    *invincibility,
-                ) {
-                    let reset = reset_positions.clone();
-                    Timeout::new(1000, move || {
-                        reset();
-                    })
-                    .forget();
-                    return;
-                }
-
-                if new_counter % 2 == 0 {
-                    let mut new_ghosts = (*ghosts).clone();
-                    game_logic::move_ghosts(&mut new_ghosts, &pacman_pos, &maze);
-                    ghosts.set(new_ghosts);
-                }
-
-                let mut new_pos = (*pacman_pos).clone();
-                let mut maze_clone = (*maze).clone();
-                let mut current_score = *score;
-
-                if game_logic::check_game_complete(&maze_clone) {
-                    game_won.set(true);
-                    return;
-                }
-
-                if let Some((next_pos, power_pellet_eaten)) = game_logic::calculate_next_position(
-                    &current_direction,
-                    &new_pos,
-                    &mut maze_clone,
-                    &mut current_score,
-                ) {
-                    new_pos = next_pos;
-
-                    if power_pellet_eaten {
-                        invincibility.increase();
-                        let invincibility_clone = invincibility.clone();
-                        Timeout::new(5000, move || {
-                            invincibility_clone.decrease();
-                        })
-                        .forget();
-                    }
+    let close_editor = {
+        let session = session.dispatcher();
+        Callback::from(move |_: MouseEvent| {
+            session.dispatch(GameAction::CloseEditor);
+        })
+    };
 
-                    pacman_pos.set(new_pos);
-                    maze.set(maze_clone);
-                    score.set(current_score);
-                }
+    let play_maze = {
+        let session = session.dispatcher();
+        Callback::from(move |maze: Maze| {
+            session.dispatch(GameAction::PlayMaze(maze));
+        })
+    };
+
+    let select_pack = {
+        let session = session.dispatcher();
+        Callback::from(move |index: usize| {
+            session.dispatch(GameAction::SelectPack(index));
+        })
+    };
+
+    let toggle_ghost_ai = {
+        let session = session.dispatcher();
+        Callback::from(move |_: MouseEvent| {
+            session.dispatch(GameAction::ToggleGhostAi);
+        })
+    };
+
+    {
+        let session = session.dispatcher();
+        let pending_direction = pending_direction.clone();
+        use_effect_with((), move |_| {
+            let interval = Interval::new(TICK_MS, move || {
+                let direction = pending_direction.borrow_mut().take();
+                session.dispatch(GameAction::Tick(Input { direction }));
             });
 
             move || drop(interval)
@@ -205,33 +238,79 @@ pub fn App() -> Html {
     }
 
     {
-        let current_direction = current_direction.clone();
-        use_effect(move || {
-            let listener = controls::setup_keyboard_controls(current_direction);
+        let pending_direction = pending_direction.clone();
+        use_effect_with((), move |_| {
+            let on_direction = Callback::from(move |direction: Direction| {
+                *pending_direction.borrow_mut() = Some(direction);
+            });
+            let listener = controls::setup_keyboard_controls(on_direction);
             move || drop(listener)
         });
     }
 
+    {
+        let session = session.dispatcher();
+        use_effect_with((), move |_| {
+            let on_toggle = {
+                let session = session.clone();
+                Callback::from(move |_| session.dispatch(GameAction::TogglePause))
+            };
+            let on_hidden = Callback::from(move |_| session.dispatch(GameAction::Pause));
+            let listeners = controls::setup_pause_controls(on_toggle, on_hidden);
+            move || drop(listeners)
+        });
+    }
+
+    let game = &session.game;
+
+    if session.editing {
+        return html! {
+            <MazeEditor
+                maze={game.layout().clone()}
+                on_play={play_maze}
+                on_close={close_editor}
+            />
+        };
+    }
+
     html! {
         <>
             <Scoreboard
-                score={*score}
-                lives={*lives}
-                restart_timer={*restart_timer}
-                game_over={*game_over}
+                score={game.score}
+                lives={game.lives}
+                life_gained={session.life_gained_ticks > 0}
+                seed={game.seed()}
+                restart_timer={game.phase == GamePhase::Respawning}
+                game_over={game.phase == GamePhase::GameOver}
                 on_restart={restart_game.clone()}
-                game_won={*game_won}
-                game_started={*game_started}
+                level={game.level}
+                level_complete={game.phase == GamePhase::LevelComplete}
+                collected_fruit={game.collected_fruit.clone()}
+                game_started={game.is_started()}
+                paused={game.is_paused()}
+                replay={session.recording()}
+                is_replaying={matches!(session.mode, PlaybackMode::Replaying(_))}
                 on_start={start_game.clone()}
+                on_edit_maze={open_editor}
+                maze_pack={session.maze_pack}
+                on_select_pack={select_pack}
+                ghost_ai={game.ghost_ai}
+                on_toggle_ghost_ai={toggle_ghost_ai}
+                on_load_replay={load_replay}
             />
             <GameBoard
-                score={*score}
-                game_over={*game_over}
-                maze={(*maze).clone()}
-                pacman_pos={(*pacman_pos).clone()}
-                ghosts={(*ghosts).clone()}
-                is_dying={*is_dying}
-                is_invincible={*invincibility > 0}
+                score={game.score}
+                game_over={game.phase == GamePhase::GameOver}
+                maze={game.maze.clone()}
+                theme={session.theme()}
+                pacman_pos={game.pacman_pos.clone()}
+                pacman_direction={game.direction}
+                pacman_motion={game.pacman_motion}
+                ghosts={game.ghosts.clone()}
+                popups={game.popups.clone()}
+                fruit={game.fruit.clone()}
+                is_dying={game.is_dying()}
+                is_invincible={game.is_invincible()}
             />
         </>
     }
@@ -0,0 +1,211 @@
+//! Ghost personalities. Each ghost slot has a `GhostBrain` that picks where
+//! the ghost heads while it is hunting, scattering or frightened; the engine
+//! still handles the house, eaten eyes and forced reversals itself.
+
+use crate::game_logic::{
+    ambush_target, direction_between, find_flee_move, find_target_move, flank_target, shy_target,
+};
+use crate::maze::Maze;
+use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+use crate::pathfinding::DistanceTable;
+use rand::RngCore;
+use std::rc::Rc;
+
+/// Everything a brain may look at, as it stood at the start of the tick.
+pub struct GhostView<'a> {
+    pub maze: &'a Maze,
+    pub pacman_pos: &'a Position,
+    pub pacman_direction: Direction,
+    pub ghosts: &'a [Ghost],
+    /// Present when ghosts should steer by shortest path rather than the
+    /// arcade's straight-line rule.
+    pub distances: Option<&'a DistanceTable>,
+}
+
+impl GhostView<'_> {
+    /// Where `id` stands, or Pac-Man's tile if that ghost isn't on the board.
+    pub fn ghost_position(&self, id: GhostId) -> &Position {
+        self.ghosts
+            .iter()
+            .find(|ghost| ghost.id == id)
+            .map_or(self.pacman_pos, |ghost| &ghost.position)
+    }
+}
+
+pub trait GhostBrain {
+    /// Direction `ghost` wants to take from its tile. Brains are asked in
+    /// Scatter, Chase and Frightened only. A direction that would reverse the
+    /// ghost or walk into a wall is ignored in favour of the first open way
+    /// in Up, Left, Down, Right order.
+    fn choose_direction(&self, ghost: &Ghost, view: &GhostView, rng: &mut dyn RngCore)
+        -> Direction;
+}
+
+/// Blinky's personality: hunts Pac-Man's own tile.
+pub struct Shadow;
+
+/// Pinky's personality: ambushes four tiles ahead of Pac-Man.
+pub struct Speedy;
+
+/// Inky's personality: flanks from the far side of Blinky.
+pub struct Bashful;
+
+/// Clyde's personality: chases from afar, loses his nerve up close.
+pub struct Pokey;
+
+impl GhostBrain for Shadow {
+    fn choose_direction(
+        &self,
+        ghost: &Ghost,
+        view: &GhostView,
+        rng: &mut dyn RngCore,
+    ) -> Direction {
+        arcade_direction(ghost, view, rng, view.pacman_pos.clone())
+    }
+}
+
+impl GhostBrain for Speedy {
+    fn choose_direction(
+        &self,
+        ghost: &Ghost,
+        view: &GhostView,
+        rng: &mut dyn RngCore,
+    ) -> Direction {
+        let target = ambush_target(view.pacman_pos, view.pacman_direction, view.maze);
+        arcade_direction(ghost, view, rng, target)
+    }
+}
+
+impl GhostBrain for Bashful {
+    fn choose_direction(
+        &self,
+        ghost: &Ghost,
+        view: &GhostView,
+        rng: &mut dyn RngCore,
+    ) -> Direction {
+        let target = flank_target(
+            view.pacman_pos,
+            view.pacman_direction,
+            view.ghost_position(GhostId::Blinky),
+            view.maze,
+        );
+        arcade_direction(ghost, view, rng, target)
+    }
+}
+
+impl GhostBrain for Pokey {
+    fn choose_direction(
+        &self,
+        ghost: &Ghost,
+        view: &GhostView,
+        rng: &mut dyn RngCore,
+    ) -> Direction {
+        arcade_direction(ghost, view, rng, shy_target(ghost, view.pacman_pos))
+    }
+}
+
+/// The arcade schedule shared by the built-in brains: flee when frightened,
+/// head for the scatter corner in Scatter (unless Blinky is Cruise Elroy, who
+/// keeps hunting) and for `chase_target` otherwise.
+pub fn arcade_direction(
+    ghost: &Ghost,
+    view: &GhostView,
+    rng: &mut dyn RngCore,
+    chase_target: Position,
+) -> Direction {
+    let next = match ghost.mode {
+        GhostMode::Frightened => find_flee_move(ghost, view.pacman_pos, view.maze, rng),
+        GhostMode::Scatter if ghost.elroy == 0 => {
+            find_target_move(ghost, &ghost.scatter_target, view.maze, view.distances)
+        }
+        _ => find_target_move(ghost, &chase_target, view.maze, view.distances),
+    };
+    next.map_or(Direction::None, |pos| {
+        direction_between(&ghost.position, &pos)
+    })
+}
+
+/// The brain behind each ghost: the arcade personality that matches it,
+/// unless a custom one has been registered for that ghost.
+#[derive(Clone, Default)]
+pub struct GhostBrains {
+    custom: Vec<(GhostId, Rc<dyn GhostBrain>)>,
+    /// The names these brains were picked by, which is what replays record.
+    names: Vec<String>,
+}
+
+impl GhostBrains {
+    /// Puts `brain` in charge of ghost `id` in place of its personality.
+    pub fn register(&mut self, id: GhostId, brain: Rc<dyn GhostBrain>) {
+        self.custom.retain(|(slot, _)| *slot != id);
+        self.custom.push((id, brain));
+    }
+
+    /// Brains picked by name, one per ghost in Blinky, Pinky, Inky, Clyde
+    /// order. Unknown names leave that ghost its own personality.
+    pub fn from_names(names: &[String]) -> Self {
+        let mut brains = Self {
+            names: names.to_vec(),
+            ..Self::default()
+        };
+        let ids = [
+            GhostId::Blinky,
+            GhostId::Pinky,
+            GhostId::Inky,
+            GhostId::Clyde,
+        ];
+        for (id, name) in ids.into_iter().zip(names) {
+            if let Some(brain) = personality(name) {
+                brains.register(id, brain);
+            }
+        }
+        brains
+    }
+
+    /// Names given to `from_names`, empty for the arcade line-up. Brains
+    /// registered directly have no name, so a replay can't bring them back.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn get(&self, id: GhostId) -> &dyn GhostBrain {
+        self.custom
+            .iter()
+            .find(|(slot, _)| *slot == id)
+            .map_or_else(|| builtin_brain(id), |(_, brain)| brain.as_ref())
+    }
+}
+
+/// Two sets are equal when every ghost has the very same brain.
+impl PartialEq for GhostBrains {
+    fn eq(&self, other: &Self) -> bool {
+        self.custom.len() == other.custom.len()
+            && self.custom.iter().all(|(id, brain)| {
+                other
+                    .custom
+                    .iter()
+                    .any(|(other_id, other_brain)| id == other_id && Rc::ptr_eq(brain, other_brain))
+            })
+    }
+}
+
+/// The arcade personality for each ghost.
+pub fn builtin_brain(id: GhostId) -> &'static dyn GhostBrain {
+    match id {
+        GhostId::Blinky => &Shadow,
+        GhostId::Pinky => &Speedy,
+        GhostId::Inky => &Bashful,
+        GhostId::Clyde => &Pokey,
+    }
+}
+
+/// A built-in personality by its arcade nickname.
+pub fn personality(name: &str) -> Option<Rc<dyn GhostBrain>> {
+    match name {
+        "shadow" => Some(Rc::new(Shadow)),
+        "speedy" => Some(Rc::new(Speedy)),
+        "bashful" => Some(Rc::new(Bashful)),
+        "pokey" => Some(Rc::new(Pokey)),
+        _ => None,
+    }
+}
@@ -0,0 +1,39 @@
+use crate::constants::game::TICK_MS;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ActorProps {
+    /// Position in tiles, fractional while gliding between tiles.
+    pub x: f64,
+    pub y: f64,
+    pub columns: usize,
+    pub rows: usize,
+    pub class: Classes,
+    pub wrapped: bool,
+    pub children: Html,
+}
+
+/// Pac-Man or a ghost drawn over the maze grid. Each tick moves it to its new
+/// sub-tile position and a CSS transition glides it there.
+#[function_component]
+pub fn Actor(props: &ActorProps) -> Html {
+    let width = 100.0 / props.columns as f64;
+    let height = 100.0 / props.rows as f64;
+    let style = format!(
+        "left: {}%; top: {}%; width: {}%; height: {}%; transition-duration: {}ms;",
+        props.x * width,
+        props.y * height,
+        width,
+        height,
+        TICK_MS
+    );
+
+    html! {
+        <div
+            class={classes!("actor", props.class.clone(), props.wrapped.then_some("wrapped"))}
+            {style}
+        >
+            {props.children.clone()}
+        </div>
+    }
+}
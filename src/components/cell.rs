@@ -1,66 +1,56 @@
-use crate::models::Ghost;
+use crate::maze::Tile;
+use crate::models::Fruit;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct CellProps {
-    pub cell_type: u8,
-    pub is_pacman: bool,
-    pub ghost: Option<Ghost>,
-    pub is_dying: bool,
-    pub is_invincible: bool,
+    pub tile: Tile,
     pub custom_style: Option<AttrValue>,
+    pub popup: Option<i32>,
+    pub fruit: Option<Fruit>,
+    #[prop_or_default]
+    pub onclick: Callback<MouseEvent>,
+    #[prop_or_default]
+    pub children: Html,
 }
 
 #[function_component]
 pub fn Cell(props: &CellProps) -> Html {
     let class = classes!(
         "cell",
-        if props.is_pacman {
-            if props.is_dying {
-                "pacman dying"
-            } else {
-                "pacman"
-            }
-        } else if props.ghost.is_some() {
-            "ghost"
-        } else {
-            match props.cell_type {
-                1 => "wall",
-                2 => "dot",
-                3 => "power-pellet",
-                _ => "empty",
-            }
+        match props.tile {
+            Tile::Wall => "wall",
+            Tile::Dot => "dot",
+            Tile::PowerPellet => "power-pellet",
+            Tile::GhostDoor => "ghost-door",
+            Tile::Tunnel => "tunnel",
+            Tile::Empty | Tile::FruitSpawn => "empty",
         }
     );
 
-    let content = if props.is_pacman {
+    let content = if let Some(fruit) = props.fruit {
         html! {
-            <>
-            <div class="pacman-body"></div>
-            <div class="pacman-eye"></div>
-            </>
-        }
-    } else if let Some(ghost) = &props.ghost {
-        let ghost_color = if props.is_invincible {
-            "#808080".to_string()
-        } else {
-            ghost.color.to_string()
-        };
-        let style = format!("background-color: {};", ghost_color);
-        html! {
-            <div class="ghost-body" {style}>{"👻"}</div>
+            <div class="fruit">{fruit.icon()}</div>
         }
     } else {
-        match props.cell_type {
-            2 => html! { "." },
-            3 => html! { "⚪" },
+        match props.tile {
+            Tile::Dot => html! { "." },
+            Tile::PowerPellet => html! { "⚪" },
             _ => html! { "" },
         }
     };
 
+    let popup = props.popup.map(|points| {
+        html! {
+            <div class="score-popup">{points}</div>
+        }
+    });
+
     html! {
-        <div class={class} style={props.custom_style.clone()}>
+        <div class={class} style={props.custom_style.clone()} onclick={props.onclick.clone()}>
             {content}
+            {popup}
+            {props.children.clone()}
         </div>
     }
 }
@@ -1,24 +1,76 @@
+use crate::components::actor::Actor;
 use crate::components::cell::Cell;
-use crate::models::{Ghost, Position};
+use crate::engine::{BonusFruit, ScorePopup};
+use crate::game_logic::{ghost_render_position, pacman_render_position};
+use crate::maze::{Maze, MazeTheme, Tile};
+use crate::models::{Direction, Ghost, GhostMode, Motion, Position};
+use std::ops::RangeInclusive;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct GameBoardProps {
     pub score: i32,
     pub game_over: bool,
-    pub maze: Vec<Vec<u8>>,
+    pub maze: Maze,
+    #[prop_or_default]
+    pub theme: MazeTheme,
     pub pacman_pos: Position,
+    pub pacman_direction: Direction,
+    pub pacman_motion: Motion,
     pub ghosts: Vec<Ghost>,
+    pub popups: Vec<ScorePopup>,
+    pub fruit: Option<BonusFruit>,
     pub is_dying: bool,
     pub is_invincible: bool,
 }
 
 #[function_component]
 pub fn GameBoard(props: &GameBoardProps) -> Html {
-    let style = format!(
-        "grid-template-columns: repeat({}, 1fr);",
-        props.maze[0].len()
+    let columns = props.maze.width();
+    let rows = props.maze.height();
+    let style = format!("grid-template-columns: repeat({}, 1fr);", columns);
+    let house = house_band(&props.maze);
+
+    let (pacman_x, pacman_y) = pacman_render_position(
+        &props.pacman_pos,
+        props.pacman_direction,
+        &props.pacman_motion,
+        &props.maze,
     );
+    let pacman = html! {
+        <Actor
+            x={pacman_x}
+            y={pacman_y}
+            {columns}
+            {rows}
+            class={classes!("pacman", props.is_dying.then_some("dying"), props.is_invincible.then_some("invincible"))}
+            wrapped={props.pacman_motion.wrapped}
+        >
+            <div class="pacman-body"></div>
+            <div class="pacman-eye"></div>
+        </Actor>
+    };
+
+    let ghosts = props.ghosts.iter().map(|ghost| {
+        let (x, y) = ghost_render_position(ghost, &props.maze);
+        let content = if ghost.mode == GhostMode::Eaten {
+            html! { <div class="ghost-eyes">{"👀"}</div> }
+        } else {
+            let ghost_color = if ghost.mode == GhostMode::Frightened {
+                "#808080"
+            } else {
+                ghost.id.color()
+            };
+            let style = format!("background-color: {};", ghost_color);
+            html! { <div class="ghost-body" {style}>{"👻"}</div> }
+        };
+
+        html! {
+            <Actor {x} {y} {columns} {rows} class={classes!("ghost")} wrapped={ghost.motion.wrapped}>
+                {content}
+            </Actor>
+        }
+    }).collect::<Html>();
 
     html! {
         <>
@@ -27,32 +79,88 @@ pub fn GameBoard(props: &GameBoardProps) -> Html {
             </style>
         <div class="maze" {style}>
             {
-                props.maze.iter().enumerate().map(|(y, row)| {
-                    row.iter().enumerate().map(|(x, &cell)| {
-                        let is_pacman = x == props.pacman_pos.x && y == props.pacman_pos.y;
-                        let ghost = props.ghosts.iter()
-                            .find(|g| g.position.x == x && g.position.y == y)
-                            .cloned();
-                        let custom_style = if y >= 7 && y <=11 && x >=2 && x <= 33 && cell == 1 {
-                            Some(AttrValue::from("background: linear-gradient(45deg, #00c0ff, #00e2f9)"))
-                        } else {
-                            None
-                        };
+                props.maze.tiles.iter().enumerate().map(|(y, row)| {
+                    row.iter().enumerate().map(|(x, &tile)| {
+                        let popup = props.popups.iter()
+                            .find(|p| p.position.x == x && p.position.y == y)
+                            .map(|p| p.points);
+                        let fruit = props.fruit.as_ref()
+                            .filter(|f| f.position.x == x && f.position.y == y)
+                            .map(|f| f.fruit);
+                        let custom_style = wall_style(&props.maze, &props.theme, house.as_ref(), x, y)
+                            .map(AttrValue::from);
 
                         html! {
                             <Cell
-                                cell_type={cell}
-                                {is_pacman}
-                                {ghost}
-                                is_dying={props.is_dying && is_pacman}
-                                is_invincible={props.is_invincible}
+                                {tile}
                                 custom_style={custom_style}
+                                {popup}
+                                {fruit}
                             />
                         }
                     }).collect::<Html>()
                 }).collect::<Html>()
             }
+            <div class="actors">
+                {ghosts}
+                {pacman}
+            </div>
         </div>
         </>
     }
 }
+
+/// Columns and rows of the ghost house, walls included. Sideways it runs
+/// between the first walls either side of the ghost spawns; up and down from
+/// a row beyond the spawns and door on each side, so it fits whether the door
+/// is on top or underneath.
+pub fn house_band(maze: &Maze) -> Option<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+    let top = maze.ghost_spawns.iter().map(|pos| pos.y).min()?;
+    let bottom = maze.ghost_spawns.iter().map(|pos| pos.y).max()?;
+    let door_rows: Vec<usize> = (top.saturating_sub(1)..=bottom + 1)
+        .filter(|&y| {
+            maze.tiles
+                .get(y)
+                .is_some_and(|row| row.contains(&Tile::GhostDoor))
+        })
+        .collect();
+    let top = door_rows.iter().copied().fold(top, usize::min);
+    let bottom = door_rows.iter().copied().fold(bottom, usize::max);
+
+    let is_wall = |x: usize, y: usize| maze.tiles[y][x] == Tile::Wall;
+    let left = maze
+        .ghost_spawns
+        .iter()
+        .map(|pos| (0..pos.x).rev().find(|&x| is_wall(x, pos.y)).unwrap_or(0))
+        .min()?;
+    let right = maze
+        .ghost_spawns
+        .iter()
+        .map(|pos| {
+            (pos.x..maze.width())
+                .find(|&x| is_wall(x, pos.y))
+                .unwrap_or(maze.width() - 1)
+        })
+        .max()?;
+
+    Some((
+        left..=right,
+        top.saturating_sub(1)..=(bottom + 1).min(maze.height() - 1),
+    ))
+}
+
+/// Inline style for the tile at `(x, y)`: the maze's wall colours, or its
+/// house colours for walls within `house`. Other tiles aren't styled.
+pub fn wall_style(
+    maze: &Maze,
+    theme: &MazeTheme,
+    house: Option<&(RangeInclusive<usize>, RangeInclusive<usize>)>,
+    x: usize,
+    y: usize,
+) -> Option<String> {
+    (maze.tiles[y][x] == Tile::Wall).then(|| {
+        let in_house =
+            house.is_some_and(|(columns, rows)| columns.contains(&x) && rows.contains(&y));
+        theme.wall_style(in_house)
+    })
+}
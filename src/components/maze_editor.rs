@@ -0,0 +1,216 @@
+use crate::components::cell::Cell;
+use crate::components::replay_controls::download;
+use crate::maze::{generate_maze, Brush, Maze, MazeDraft, Tile};
+use gloo::file::callbacks::{read_as_text, FileReader};
+use gloo::file::{Blob, File, ObjectUrl};
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+const MAZE_FILE_NAME: &str = "pacman-maze.txt";
+
+const PALETTE: [(Brush, &str); 9] = [
+    (Brush::Tile(Tile::Wall), "Wall"),
+    (Brush::Tile(Tile::Dot), "Dot"),
+    (Brush::Tile(Tile::PowerPellet), "Power Pellet"),
+    (Brush::Tile(Tile::Empty), "Empty"),
+    (Brush::Tile(Tile::GhostDoor), "Ghost Door"),
+    (Brush::Tile(Tile::Tunnel), "Tunnel"),
+    (Brush::Tile(Tile::FruitSpawn), "Fruit"),
+    (Brush::PacmanSpawn, "Pac-Man"),
+    (Brush::GhostSpawn, "Ghost"),
+];
+
+/// Changes to the draft. Each is applied to the draft as it is when the
+/// action arrives, so a file finishing loading can't roll back edits made
+/// while it was read.
+pub enum DraftAction {
+    Paint(usize, usize, Brush),
+    Resize(isize, isize),
+    Undo,
+    Redo,
+    Load(String),
+    Generate(u64),
+}
+
+impl Reducible for MazeDraft {
+    type Action = DraftAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut draft = (*self).clone();
+        match action {
+            DraftAction::Paint(x, y, brush) => draft.paint(x, y, brush),
+            DraftAction::Resize(grow_width, grow_height) => draft.resize(
+                draft.width().saturating_add_signed(grow_width),
+                draft.height().saturating_add_signed(grow_height),
+            ),
+            DraftAction::Undo => draft.undo(),
+            DraftAction::Redo => draft.redo(),
+            DraftAction::Load(text) => draft.load(&text),
+            DraftAction::Generate(seed) => {
+                let maze = generate_maze(draft.width(), draft.height(), seed);
+                draft.load(&maze.to_string());
+            }
+        }
+        Rc::new(draft)
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MazeEditorProps {
+    /// The maze the editor opens with.
+    pub maze: Maze,
+    pub on_play: Callback<Maze>,
+    pub on_close: Callback<MouseEvent>,
+}
+
+#[function_component]
+pub fn MazeEditor(props: &MazeEditorProps) -> Html {
+    let draft = use_reducer(|| MazeDraft::from_text(&props.maze.to_string()));
+    let brush = use_state(|| Brush::Tile(Tile::Wall));
+    // As in the replay controls, the download URL and file reader have to
+    // outlive their handlers.
+    let export_url = use_mut_ref(|| None::<ObjectUrl>);
+    let reader = use_mut_ref(|| None::<FileReader>);
+
+    let edit = |action: fn() -> DraftAction| {
+        let draft = draft.dispatcher();
+        Callback::from(move |_: MouseEvent| draft.dispatch(action()))
+    };
+
+    let onclick_export = {
+        let draft = draft.clone();
+        Callback::from(move |_: MouseEvent| {
+            let blob = Blob::new_with_options(draft.text().as_str(), Some("text/plain"));
+            let url = ObjectUrl::from(blob);
+            download(&url, MAZE_FILE_NAME);
+            *export_url.borrow_mut() = Some(url);
+        })
+    };
+
+    let onchange_import = {
+        let draft = draft.dispatcher();
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let draft = draft.clone();
+                let task = read_as_text(&File::from(file), move |result| {
+                    if let Ok(text) = result {
+                        draft.dispatch(DraftAction::Load(text));
+                    }
+                });
+                *reader.borrow_mut() = Some(task);
+            }
+            input.set_value("");
+        })
+    };
+
+    let onclick_generate = {
+        let draft = draft.dispatcher();
+        Callback::from(move |_: MouseEvent| draft.dispatch(DraftAction::Generate(rand::random())))
+    };
+
+    let validation = draft.validate();
+    let onclick_play = {
+        let on_play = props.on_play.clone();
+        let maze = validation.clone().ok();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(maze) = &maze {
+                on_play.emit(maze.clone());
+            }
+        })
+    };
+
+    let palette = PALETTE
+        .iter()
+        .map(|&(option, label)| {
+            let onclick = {
+                let brush = brush.clone();
+                Callback::from(move |_: MouseEvent| brush.set(option))
+            };
+            html! {
+                <button
+                    class={classes!("replay-button", (*brush == option).then_some("selected"))}
+                    {onclick}
+                >
+                    {label}
+                </button>
+            }
+        })
+        .collect::<Html>();
+
+    let style = format!("grid-template-columns: repeat({}, 1fr);", draft.width());
+    let squares = (0..draft.height())
+        .flat_map(|y| (0..draft.width()).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let symbol = draft.symbol(x, y);
+            let onclick = {
+                let draft = draft.dispatcher();
+                let brush = *brush;
+                Callback::from(move |_: MouseEvent| draft.dispatch(DraftAction::Paint(x, y, brush)))
+            };
+            let marker = match symbol {
+                'P' => html! { <div class="spawn-marker">{"ᗧ"}</div> },
+                'G' => html! { <div class="spawn-marker">{"👻"}</div> },
+                _ => html! {},
+            };
+            html! {
+                <Cell
+                    tile={Tile::from_symbol(symbol).unwrap_or(Tile::Empty)}
+                    custom_style={None::<AttrValue>}
+                    popup={None::<i32>}
+                    fruit={None}
+                    {onclick}
+                >
+                    {marker}
+                </Cell>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="maze-editor">
+            <style>
+                {include_str!("../styles/game.css")}
+            </style>
+            <div class="editor-toolbar">
+                {palette}
+            </div>
+            <div class="editor-toolbar">
+                <span>{format!("{} x {}", draft.width(), draft.height())}</span>
+                <button class="replay-button" onclick={edit(|| DraftAction::Resize(-1, 0))}>{"Narrower"}</button>
+                <button class="replay-button" onclick={edit(|| DraftAction::Resize(1, 0))}>{"Wider"}</button>
+                <button class="replay-button" onclick={edit(|| DraftAction::Resize(0, -1))}>{"Shorter"}</button>
+                <button class="replay-button" onclick={edit(|| DraftAction::Resize(0, 1))}>{"Taller"}</button>
+                <button class="replay-button" onclick={edit(|| DraftAction::Undo)} disabled={!draft.can_undo()}>
+                    {"Undo"}
+                </button>
+                <button class="replay-button" onclick={edit(|| DraftAction::Redo)} disabled={!draft.can_redo()}>
+                    {"Redo"}
+                </button>
+                <button class="replay-button" onclick={onclick_generate}>{"Random Maze"}</button>
+                <button class="replay-button" onclick={onclick_export}>{"Export Maze"}</button>
+                <label class="replay-button">
+                    {"Import Maze"}
+                    <input type="file" accept=".txt,text/plain" onchange={onchange_import} />
+                </label>
+            </div>
+            <div class="maze editor-grid" {style}>
+                {squares}
+            </div>
+            <div class="editor-toolbar">
+                <button class="start-button" onclick={onclick_play} disabled={validation.is_err()}>
+                    {"Play this maze"}
+                </button>
+                <button class="replay-button" onclick={props.on_close.clone()}>{"Back to Game"}</button>
+            </div>
+            <ul class="editor-errors">
+                {
+                    for validation.err().into_iter().flatten().map(|error| html! {
+                        <li>{error.to_string()}</li>
+                    })
+                }
+            </ul>
+        </div>
+    }
+}
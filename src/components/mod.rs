@@ -1,3 +1,6 @@
+pub mod actor;
 pub mod cell;
 pub mod game_board;
+pub mod maze_editor;
+pub mod replay_controls;
 pub mod scoreboard;
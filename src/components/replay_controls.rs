@@ -0,0 +1,98 @@
+use crate::engine::Replay;
+use gloo::file::callbacks::{read_as_text, FileReader};
+use gloo::file::{Blob, File, ObjectUrl};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, HtmlInputElement};
+use yew::prelude::*;
+
+const REPLAY_FILE_NAME: &str = "pacman-replay.json";
+
+#[derive(Properties, PartialEq)]
+pub struct ReplayControlsProps {
+    pub replay: Option<Replay>,
+    pub is_replaying: bool,
+    pub on_load: Callback<Replay>,
+}
+
+#[function_component]
+pub fn ReplayControls(props: &ReplayControlsProps) -> Html {
+    // The object URL and file reader must outlive the click/change handlers,
+    // otherwise the download is revoked or the read cancelled mid-flight.
+    let export_url = use_mut_ref(|| None::<ObjectUrl>);
+    let reader = use_mut_ref(|| None::<FileReader>);
+    let error = use_state(|| None::<String>);
+
+    let onclick_export = {
+        let replay = props.replay.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(replay) = &replay {
+                let blob = Blob::new_with_options(replay.to_json().as_str(), Some("application/json"));
+                let url = ObjectUrl::from(blob);
+                download(&url, REPLAY_FILE_NAME);
+                *export_url.borrow_mut() = Some(url);
+            }
+        })
+    };
+
+    let onchange_import = {
+        let on_load = props.on_load.clone();
+        let error = error.clone();
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let on_load = on_load.clone();
+                let error = error.clone();
+                let task = read_as_text(&File::from(file), move |result| {
+                    match result
+                        .map_err(|err| err.to_string())
+                        .and_then(|json| Replay::from_json(&json).map_err(|err| err.to_string()))
+                    {
+                        Ok(replay) => {
+                            error.set(None);
+                            on_load.emit(replay);
+                        }
+                        Err(err) => error.set(Some(err)),
+                    }
+                });
+                *reader.borrow_mut() = Some(task);
+            }
+            input.set_value("");
+        })
+    };
+
+    html! {
+        <div class="replay-controls">
+            if props.is_replaying {
+                <div class="replay-badge">{"Replay"}</div>
+            }
+            <button
+                class="replay-button"
+                onclick={onclick_export}
+                disabled={props.replay.is_none()}
+            >
+                {"Export Replay"}
+            </button>
+            <label class="replay-button">
+                {"Import Replay"}
+                <input type="file" accept=".json,application/json" onchange={onchange_import} />
+            </label>
+            if let Some(err) = &*error {
+                <div class="replay-error">{err}</div>
+            }
+        </div>
+    }
+}
+
+pub fn download(url: &ObjectUrl, file_name: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let Ok(link) = document.create_element("a") else {
+        return;
+    };
+    let _ = link.set_attribute("href", url);
+    let _ = link.set_attribute("download", file_name);
+    if let Some(link) = link.dyn_ref::<HtmlElement>() {
+        link.click();
+    }
+}
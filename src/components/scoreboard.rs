@@ -1,3 +1,9 @@
+use crate::components::replay_controls::ReplayControls;
+use crate::constants::game::MAX_LIVES_DISPLAYED;
+use crate::engine::Replay;
+use crate::maze::MAZE_PACKS;
+use crate::models::Fruit;
+use crate::pathfinding::GhostAi;
 use web_sys::MouseEvent;
 use yew::prelude::*;
 
@@ -5,30 +11,58 @@ use yew::prelude::*;
 pub struct ScoreboardProps {
     pub score: i32,
     pub lives: i32,
+    pub life_gained: bool,
+    pub seed: u64,
     pub restart_timer: bool,
     pub game_over: bool,
-    pub game_won: bool,
+    pub level: u32,
+    pub level_complete: bool,
+    pub collected_fruit: Vec<Fruit>,
     pub game_started: bool,
+    pub paused: bool,
+    pub replay: Option<Replay>,
+    pub is_replaying: bool,
     pub on_restart: Callback<MouseEvent>,  
-    pub on_start: Callback<MouseEvent>,}
+    pub on_start: Callback<MouseEvent>,
+    pub on_edit_maze: Callback<MouseEvent>,
+    /// Index into `MAZE_PACKS`, or `None` for a maze from the editor.
+    pub maze_pack: Option<usize>,
+    pub on_select_pack: Callback<usize>,
+    pub ghost_ai: GhostAi,
+    pub on_toggle_ghost_ai: Callback<MouseEvent>,
+    pub on_load_replay: Callback<Replay>,
+}
 
 #[function_component]
 pub fn Scoreboard(props: &ScoreboardProps) -> Html {
     let onclick_restart = props.on_restart.clone();
     let onclick_start = props.on_start.clone();
+    let onclick_edit_maze = props.on_edit_maze.clone();
 
     html! {
         <div class="game-info">
             <div class="score">
                 <div>{"Score: "}{{format!("{:05}", props.score)}}</div>
-                    <div class="lives">
-                        <div class="cell pacman">
-                            <div class="pacman-body"></div>
-                            <div class="pacman-eye"></div>
-                        </div>
-                        {":"}
-                    <div class="lives-count">{props.lives}</div>
+                <div class={classes!("lives", props.life_gained.then_some("life-gained"))}>
+                    {
+                        for (0..props.lives.clamp(0, MAX_LIVES_DISPLAYED)).map(|_| html! {
+                            <div class="cell pacman">
+                                <div class="pacman-body"></div>
+                                <div class="pacman-eye"></div>
+                            </div>
+                        })
+                    }
                 </div>
+                <div class="level">{"Level: "}{props.level}</div>
+                <div class="collected-fruit">
+                    { for props.collected_fruit.iter().rev().take(7).rev().map(|fruit| html! { <span>{fruit.icon()}</span> }) }
+                </div>
+                <div class="seed">{"Seed: "}{props.seed}</div>
+                <ReplayControls
+                    replay={props.replay.clone()}
+                    is_replaying={props.is_replaying}
+                    on_load={props.on_load_replay.clone()}
+                />
             </div>
             {
                 if !props.game_started {
@@ -37,20 +71,46 @@ pub fn Scoreboard(props: &ScoreboardProps) -> Html {
                             <button onclick={onclick_start} class="start-button">
                                 {"Start Game"}
                             </button>
+                            <button onclick={onclick_edit_maze} class="replay-button">
+                                {"Edit Maze"}
+                            </button>
+                            <button onclick={props.on_toggle_ghost_ai.clone()} class="replay-button">
+                                {
+                                    match props.ghost_ai {
+                                        GhostAi::Greedy => "Ghosts: Arcade",
+                                        GhostAi::ShortestPath => "Ghosts: Pathfinding",
+                                    }
+                                }
+                            </button>
+                            <div class="maze-packs">
+                                {
+                                    for MAZE_PACKS.iter().enumerate().map(|(index, pack)| {
+                                        let on_select_pack = props.on_select_pack.clone();
+                                        let selected = props.maze_pack == Some(index);
+                                        html! {
+                                            <button
+                                                class={classes!("replay-button", selected.then_some("selected"))}
+                                                onclick={Callback::from(move |_| on_select_pack.emit(index))}
+                                            >
+                                                {pack.name}
+                                            </button>
+                                        }
+                                    })
+                                }
+                            </div>
                         </div>
                     }
+                } else if props.paused {
+                    html! {
+                        <div class="message">{"Paused"}</div>
+                    }
                 } else if props.restart_timer {
                     html! {
                         <div class="message">{"Get Ready!"}</div>
                     }
-                } else if props.game_won {  
+                } else if props.level_complete {
                     html! {
-                        <>
-                            <div class="victory">{"Victory!"}</div>
-                            <button onclick={onclick_restart} class="restart-button">
-                                {"Play Again"}
-                            </button>
-                        </>
+                        <div class="victory">{format!("Level {} Complete!", props.level)}</div>
                     }
                 } else if props.game_over {
                     html! {
@@ -0,0 +1,29 @@
+pub const TICK_MS: u32 = 150;
+
+/// Fixed-point resolution of actor positions: one tile is this many sub-tiles.
+pub const SUBTILES_PER_TILE: u32 = 20;
+
+pub const STARTING_LIVES: i32 = 3;
+pub const MAX_LIVES_DISPLAYED: i32 = 5;
+pub const EXTRA_LIFE_SCORE: i32 = 10_000;
+
+pub const DEATH_DELAY_MS: u32 = 1000;
+pub const RESPAWN_DELAY_MS: u32 = 3000;
+pub const LEVEL_COMPLETE_DELAY_MS: u32 = 2000;
+pub const SCORE_POPUP_MS: u32 = 1000;
+pub const EXTRA_LIFE_FLASH_MS: u32 = 1500;
+pub const FRUIT_MS: u32 = 9500;
+
+pub const DOT_POINTS: i32 = 10;
+pub const POWER_PELLET_POINTS: i32 = 50;
+
+/// Dots eaten in a level at which a bonus fruit appears.
+pub const FRUIT_DOT_THRESHOLDS: [u32; 2] = [70, 170];
+
+pub const FRIGHTENED_WANDER_CHANCE: f64 = 0.25;
+
+/// Converts a wall-clock duration into a whole number of engine ticks,
+/// rounding up so a delay never finishes early.
+pub const fn ticks_from_ms(ms: u32) -> u32 {
+    ms.div_ceil(TICK_MS)
+}
@@ -1 +1 @@
-pub mod maze;
+pub mod game;
@@ -2,7 +2,7 @@ use crate::models::Direction;
 use gloo::events::EventListener;
 use wasm_bindgen::JsCast;
 use web_sys::KeyboardEvent;
-use yew::UseStateHandle;
+use yew::Callback;
 
 pub fn get_direction_from_key(key: &str) -> Option<Direction> {
     match key {
@@ -14,15 +14,44 @@ pub fn get_direction_from_key(key: &str) -> Option<Direction> {
     }
 }
 
-pub fn setup_keyboard_controls(current_direction: UseStateHandle<Direction>) -> EventListener {
+pub fn is_pause_key(key: &str) -> bool {
+    matches!(key, "p" | "P" | "Escape")
+}
+
+pub fn setup_keyboard_controls(on_direction: Callback<Direction>) -> EventListener {
     let document = web_sys::window().unwrap().document().unwrap();
 
     let handler = move |event: &web_sys::Event| {
         let event = event.dyn_ref::<KeyboardEvent>().unwrap();
         if let Some(new_direction) = get_direction_from_key(&event.key()) {
-            current_direction.set(new_direction);
+            on_direction.emit(new_direction);
         }
     };
 
     EventListener::new(&document, "keydown", handler)
 }
+
+/// Calls `on_toggle` when a pause key is pressed and `on_hidden` when the page
+/// is hidden, so a game left in a background tab waits for the player.
+pub fn setup_pause_controls(
+    on_toggle: Callback<()>,
+    on_hidden: Callback<()>,
+) -> [EventListener; 2] {
+    let document = web_sys::window().unwrap().document().unwrap();
+
+    let keydown = EventListener::new(&document, "keydown", move |event| {
+        let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+        if is_pause_key(&event.key()) {
+            on_toggle.emit(());
+        }
+    });
+
+    let hidden_document = document.clone();
+    let visibility = EventListener::new(&document, "visibilitychange", move |_| {
+        if hidden_document.hidden() {
+            on_hidden.emit(());
+        }
+    });
+
+    [keydown, visibility]
+}
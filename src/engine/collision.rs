@@ -0,0 +1,57 @@
+//! Swept collisions. Every actor's tiles over a tick are kept as a trail and
+//! compared once everyone has moved, so Pac-Man and a ghost can't slip
+//! through each other by swapping tiles within one tick.
+
+use crate::models::Position;
+
+/// Whether two actors that covered the trails `a` and `b` this tick met. A
+/// trail is the tile the actor started on followed by each tile it stepped
+/// onto, with the steps spread evenly over the tick. They meet when they are
+/// on the same tile at the same time, which covers ending on one tile,
+/// running into an actor standing still and crossing mid-trail, or when they
+/// swap tiles by crossing the same edge from opposite ends.
+fn trails_meet(a: &[Position], b: &[Position]) -> bool {
+    let swapped = a.windows(2).any(|a_step| {
+        b.windows(2)
+            .any(|b_step| a_step[0] == b_step[1] && a_step[1] == b_step[0])
+    });
+    let scale = 2 * steps(a).max(1) * steps(b).max(1);
+    let shared = spans(a, scale).any(|(a_tile, a_from, a_to)| {
+        spans(b, scale)
+            .any(|(b_tile, b_from, b_to)| a_tile == b_tile && a_from < b_to && b_from < a_to)
+    });
+    swapped || shared
+}
+
+fn steps(trail: &[Position]) -> usize {
+    trail.len().saturating_sub(1)
+}
+
+/// Each tile of `trail` with the part of the tick, out of `scale`, the actor
+/// spends on it: from halfway along the step in until halfway along the step
+/// out, or from the start or to the end of the tick for the first and last.
+fn spans(trail: &[Position], scale: usize) -> impl Iterator<Item = (&Position, usize, usize)> {
+    let steps = steps(trail);
+    let half_step = scale / (2 * steps.max(1));
+    trail.iter().enumerate().map(move |(index, tile)| {
+        let from = if index == 0 {
+            0
+        } else {
+            (2 * index - 1) * half_step
+        };
+        let to = if index == steps {
+            scale
+        } else {
+            (2 * index + 1) * half_step
+        };
+        (tile, from, to)
+    })
+}
+
+/// For each ghost trail, whether that ghost met Pac-Man this tick.
+pub fn ghosts_met(pacman_trail: &[Position], ghost_trails: &[Vec<Position>]) -> Vec<bool> {
+    ghost_trails
+        .iter()
+        .map(|trail| trails_meet(pacman_trail, trail))
+        .collect()
+}
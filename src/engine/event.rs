@@ -0,0 +1,26 @@
+use crate::models::{Fruit, GhostId, Position};
+
+/// Something noteworthy that happened during a single call to `GameState::step`.
+/// The engine never touches the UI; front-ends react to these instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    DotEaten(Position),
+    PowerPelletEaten(Position),
+    PowerPelletExpired,
+    GhostEaten {
+        ghost: GhostId,
+        position: Position,
+        points: i32,
+    },
+    FruitSpawned(Fruit),
+    FruitEaten {
+        fruit: Fruit,
+        points: i32,
+    },
+    FruitExpired,
+    ExtraLife,
+    PacmanCaught,
+    Respawned,
+    GameOver,
+    LevelCleared(u32),
+}
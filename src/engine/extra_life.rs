@@ -0,0 +1,33 @@
+use crate::constants::game::EXTRA_LIFE_SCORE;
+
+/// When Pac-Man earns a bonus life: once at `threshold` points, or every
+/// `threshold` points when `repeating` is set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtraLifeRule {
+    pub threshold: i32,
+    pub repeating: bool,
+}
+
+impl Default for ExtraLifeRule {
+    fn default() -> Self {
+        Self {
+            threshold: EXTRA_LIFE_SCORE,
+            repeating: false,
+        }
+    }
+}
+
+impl ExtraLifeRule {
+    /// Number of lives earned by going from `before` to `after` points.
+    pub fn lives_earned(&self, before: i32, after: i32) -> i32 {
+        if self.threshold <= 0 || after <= before {
+            return 0;
+        }
+        let crossings = |score: i32| score / self.threshold;
+        if self.repeating {
+            crossings(after) - crossings(before)
+        } else {
+            i32::from(before < self.threshold && after >= self.threshold)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::constants::game::ticks_from_ms;
+use crate::models::{Ghost, GhostId, GhostMode};
+
+/// Order in which waiting ghosts are considered for release.
+const RELEASE_ORDER: [GhostId; 3] = [GhostId::Pinky, GhostId::Inky, GhostId::Clyde];
+
+/// Dots the shared counter must reach to release each ghost after a life is
+/// lost, in `RELEASE_ORDER`.
+const GLOBAL_DOT_LIMITS: [u32; 3] = [7, 17, 32];
+
+fn dot_limit(level: u32, ghost: GhostId) -> u32 {
+    match (level, ghost) {
+        (0 | 1, GhostId::Inky) => 30,
+        (0 | 1, GhostId::Clyde) => 60,
+        (2, GhostId::Clyde) => 50,
+        _ => 0,
+    }
+}
+
+fn idle_limit_ticks(level: u32) -> u32 {
+    ticks_from_ms(if level < 5 { 4000 } else { 3000 })
+}
+
+fn slot(ghost: GhostId) -> usize {
+    RELEASE_ORDER.iter().position(|&id| id == ghost).unwrap_or(0)
+}
+
+/// Decides when ghosts waiting in the house may leave, using the arcade rules:
+/// the first waiting ghost counts the dots Pac-Man eats against its own limit,
+/// a shared counter takes over after a life is lost, and a ghost is forced out
+/// if Pac-Man goes too long without eating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostHouse {
+    level: u32,
+    dot_counters: [u32; 3],
+    global_counter: Option<u32>,
+    idle_ticks: u32,
+}
+
+impl GhostHouse {
+    pub fn new(level: u32) -> Self {
+        Self {
+            level,
+            dot_counters: [0; 3],
+            global_counter: None,
+            idle_ticks: 0,
+        }
+    }
+
+    pub fn on_life_lost(&mut self) {
+        self.global_counter = Some(0);
+        self.idle_ticks = 0;
+    }
+
+    pub fn on_dot_eaten(&mut self, ghosts: &[Ghost]) {
+        self.idle_ticks = 0;
+        if let Some(counter) = self.global_counter.as_mut() {
+            *counter += 1;
+        } else if let Some(ghost) = preferred_ghost(ghosts) {
+            self.dot_counters[slot(ghost)] += 1;
+        }
+    }
+
+    /// Advances the idle timer and returns the ghost to release this tick.
+    pub fn tick(&mut self, ghosts: &[Ghost]) -> Option<GhostId> {
+        self.idle_ticks += 1;
+        let ghost = preferred_ghost(ghosts)?;
+
+        if self.idle_ticks >= idle_limit_ticks(self.level) {
+            self.idle_ticks = 0;
+            return Some(ghost);
+        }
+
+        match self.global_counter {
+            Some(counter) => {
+                let release = counter >= GLOBAL_DOT_LIMITS[slot(ghost)];
+                if release && ghost == GhostId::Clyde {
+                    // Once Clyde is out the per-ghost counters take over again.
+                    self.global_counter = None;
+                }
+                release.then_some(ghost)
+            }
+            None => {
+                (self.dot_counters[slot(ghost)] >= dot_limit(self.level, ghost)).then_some(ghost)
+            }
+        }
+    }
+}
+
+fn preferred_ghost(ghosts: &[Ghost]) -> Option<GhostId> {
+    RELEASE_ORDER.into_iter().find(|&id| {
+        ghosts
+            .iter()
+            .any(|ghost| ghost.id == id && ghost.mode == GhostMode::InHouse)
+    })
+}
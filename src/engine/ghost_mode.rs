@@ -0,0 +1,90 @@
+use crate::constants::game::ticks_from_ms;
+use crate::models::GhostMode;
+
+type Schedule = [(GhostMode, u32); 7];
+
+// Arcade scatter/chase timings in milliseconds. After the last entry ghosts
+// chase indefinitely.
+const LEVEL_1_SCHEDULE: Schedule = [
+    (GhostMode::Scatter, 7000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 7000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 5000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 5000),
+];
+
+const LEVEL_2_TO_4_SCHEDULE: Schedule = [
+    (GhostMode::Scatter, 7000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 7000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 5000),
+    (GhostMode::Chase, 1_033_000),
+    (GhostMode::Scatter, 17),
+];
+
+const LEVEL_5_SCHEDULE: Schedule = [
+    (GhostMode::Scatter, 5000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 5000),
+    (GhostMode::Chase, 20000),
+    (GhostMode::Scatter, 5000),
+    (GhostMode::Chase, 1_037_000),
+    (GhostMode::Scatter, 17),
+];
+
+fn schedule_for_level(level: u32) -> &'static Schedule {
+    match level {
+        0 | 1 => &LEVEL_1_SCHEDULE,
+        2..=4 => &LEVEL_2_TO_4_SCHEDULE,
+        _ => &LEVEL_5_SCHEDULE,
+    }
+}
+
+/// Tracks which of Scatter or Chase the ghosts should be in. The clock is not
+/// advanced while ghosts are frightened, matching the arcade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeClock {
+    schedule: &'static Schedule,
+    index: usize,
+    remaining_ticks: u32,
+}
+
+impl ModeClock {
+    pub fn new(level: u32) -> Self {
+        let schedule = schedule_for_level(level);
+        Self {
+            schedule,
+            index: 0,
+            remaining_ticks: ticks_from_ms(schedule[0].1),
+        }
+    }
+
+    pub fn mode(&self) -> GhostMode {
+        self.schedule
+            .get(self.index)
+            .map_or(GhostMode::Chase, |&(mode, _)| mode)
+    }
+
+    /// Advances one tick and returns the new mode when a phase boundary is hit.
+    pub fn tick(&mut self) -> Option<GhostMode> {
+        if self.index >= self.schedule.len() {
+            return None;
+        }
+
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        if self.remaining_ticks > 0 {
+            return None;
+        }
+
+        let previous = self.mode();
+        self.index += 1;
+        if let Some(&(_, duration)) = self.schedule.get(self.index) {
+            self.remaining_ticks = ticks_from_ms(duration);
+        }
+        let mode = self.mode();
+        (mode != previous).then_some(mode)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::constants::game::{ticks_from_ms, SUBTILES_PER_TILE};
+use crate::models::Fruit;
+
+/// How one level plays. Speeds are percentages of Pac-Man's top speed of one
+/// tile per tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelSpec {
+    pub pacman_speed: u32,
+    pub pacman_frightened_speed: u32,
+    pub ghost_speed: u32,
+    pub ghost_frightened_speed: u32,
+    pub ghost_tunnel_speed: u32,
+    /// Blinky becomes Cruise Elroy once this few dots remain, and speeds up
+    /// again at half as many.
+    pub elroy_dots: usize,
+    pub elroy_speeds: [u32; 2],
+    pub frightened_ms: u32,
+    pub fruit: Fruit,
+}
+
+// Frightened time shrinks level by level, with the arcade's odd reprieves.
+const FRIGHTENED_MS: [u32; 18] = [
+    5000, 4000, 3000, 2000, 2000, 4000, 2000, 2000, 1000, 4000, 2000, 1000, 1000, 3000, 1000, 1000,
+    1000, 1000,
+];
+
+impl LevelSpec {
+    pub fn for_level(level: u32) -> Self {
+        let index = level.max(1) as usize - 1;
+        let frightened_ms = FRIGHTENED_MS[index.min(FRIGHTENED_MS.len() - 1)];
+        let (pacman_speed, ghost_speed, ghost_frightened_speed, ghost_tunnel_speed) = match level {
+            0 | 1 => (100, 50, 40, 25),
+            2..=4 => (100, 60, 45, 30),
+            5..=20 => (100, 70, 50, 35),
+            _ => (90, 70, 50, 35),
+        };
+        let elroy_dots = match level {
+            0 | 1 => 20,
+            2 => 30,
+            3..=5 => 40,
+            6..=8 => 50,
+            9..=11 => 60,
+            12..=14 => 80,
+            15..=18 => 100,
+            _ => 120,
+        };
+        Self {
+            pacman_speed,
+            pacman_frightened_speed: 100,
+            ghost_speed,
+            ghost_frightened_speed,
+            ghost_tunnel_speed,
+            elroy_dots,
+            elroy_speeds: [ghost_speed + 5, ghost_speed + 10],
+            frightened_ms,
+            fruit: Fruit::for_level(level),
+        }
+    }
+
+    /// Cruise Elroy stage for Blinky with `remaining_dots` left on the board.
+    pub fn elroy_stage(&self, remaining_dots: usize) -> u8 {
+        if remaining_dots <= self.elroy_dots / 2 {
+            2
+        } else if remaining_dots <= self.elroy_dots {
+            1
+        } else {
+            0
+        }
+    }
+
+    pub fn frightened_ticks(&self) -> u32 {
+        ticks_from_ms(self.frightened_ms)
+    }
+}
+
+/// Sub-tiles travelled per tick at `speed` percent of top speed.
+pub fn subtiles_per_tick(speed: u32) -> u32 {
+    SUBTILES_PER_TILE * speed / 100
+}
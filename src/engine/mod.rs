@@ -0,0 +1,26 @@
+//! Framework-free game engine. `GameState` owns every piece of game state and
+//! `GameState::step` advances it by one tick; the Yew `App` only drives it.
+
+mod collision;
+mod event;
+mod extra_life;
+mod ghost_house;
+mod ghost_mode;
+mod level;
+mod replay;
+mod rng;
+mod speed;
+mod state;
+mod timers;
+
+pub use collision::ghosts_met;
+pub use event::GameEvent;
+pub use extra_life::ExtraLifeRule;
+pub use ghost_house::GhostHouse;
+pub use ghost_mode::ModeClock;
+pub use level::{subtiles_per_tick, LevelSpec};
+pub use replay::{Replay, ReplayPlayer};
+pub use rng::GameRng;
+pub use speed::{ghost_speed, pacman_speed};
+pub use state::{BonusFruit, GamePhase, GameState, Input, ScorePopup};
+pub use timers::{Timer, TimerWheel};
@@ -0,0 +1,196 @@
+use super::{GameState, Input};
+use crate::brain::GhostBrains;
+use crate::maze::{parse_maze, Maze, MazeError};
+use crate::models::Direction;
+use crate::pathfinding::GhostAi;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Format of saved replays. Bump it whenever a release changes what a
+/// recording plays back as, so older files are refused instead of desyncing.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Everything needed to reproduce a game: the seed it started from, the text
+/// of each maze it cycles through unless it is just the classic maze, how the
+/// ghosts steered and which brains they played with, and every direction
+/// change, keyed by the tick it was fed into `GameState::step`. Ticks without
+/// input are not stored, which keeps replay files small.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mazes: Vec<String>,
+    #[serde(default)]
+    pub ghost_ai: GhostAi,
+    /// Brain names as given by `?ghosts=`, empty for the arcade line-up.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub brains: Vec<String>,
+    pub length: u32,
+    pub inputs: Vec<(u32, Direction)>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            mazes: Vec::new(),
+            ghost_ai: GhostAi::default(),
+            brains: Vec::new(),
+            length: 0,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// A recording of `game` from its start, keeping its mazes unless it is
+    /// played on the classic maze alone.
+    pub fn for_game(game: &GameState) -> Self {
+        let layouts = game.layouts();
+        let classic_only = layouts == [Maze::classic()];
+        Self {
+            mazes: if classic_only {
+                Vec::new()
+            } else {
+                layouts.iter().map(Maze::to_string).collect()
+            },
+            ghost_ai: game.ghost_ai,
+            brains: game.brains.names().to_vec(),
+            ..Self::new(game.seed())
+        }
+    }
+
+    /// Appends the input fed to the next tick.
+    pub fn record(&mut self, input: Input) {
+        if let Some(direction) = input.direction {
+            self.inputs.push((self.length, direction));
+        }
+        self.length += 1;
+    }
+
+    /// The state a recording starts from; stepping it with `ReplayPlayer`
+    /// inputs reproduces the original run.
+    pub fn initial_state(&self) -> GameState {
+        let mazes = if self.mazes.is_empty() {
+            vec![Maze::classic()]
+        } else {
+            self.mazes
+                .iter()
+                .map(|text| parse_maze(text).expect("replay mazes are checked on load"))
+                .collect()
+        };
+        let mut state = GameState::from_mazes(mazes, self.seed);
+        state.ghost_ai = self.ghost_ai;
+        state.brains = GhostBrains::from_names(&self.brains);
+        state.start();
+        state
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("replay is always serializable")
+    }
+
+    /// Reads a replay file, refusing anything that wouldn't play back the
+    /// game it was recorded from.
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        let replay: Self =
+            serde_json::from_str(json).map_err(|err| ReplayError::Json(err.to_string()))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::Version(replay.version));
+        }
+        for (index, text) in replay.mazes.iter().enumerate() {
+            if let Err(errors) = parse_maze(text) {
+                return Err(ReplayError::Maze(index + 1, errors[0].clone()));
+            }
+        }
+        // The player walks the inputs in step with the ticks, so anything it
+        // would skip means the file was edited or damaged.
+        for pair in replay.inputs.windows(2) {
+            if pair[1].0 <= pair[0].0 {
+                return Err(ReplayError::InputsOutOfOrder(pair[1].0));
+            }
+        }
+        if let Some(&(tick, _)) = replay
+            .inputs
+            .last()
+            .filter(|(tick, _)| *tick >= replay.length)
+        {
+            return Err(ReplayError::InputAfterLength(tick));
+        }
+        Ok(replay)
+    }
+}
+
+/// Why a replay file was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    Json(String),
+    Version(u32),
+    /// A bad maze, numbered from 1.
+    Maze(usize, MazeError),
+    /// An input at this tick repeats or comes before the one listed above it.
+    InputsOutOfOrder(u32),
+    /// An input at this tick, which the recording never reaches.
+    InputAfterLength(u32),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Json(message) => write!(f, "{}", message),
+            ReplayError::Version(version) => write!(f, "unsupported replay version {}", version),
+            ReplayError::Maze(number, error) => {
+                write!(f, "invalid replay maze {}: {}", number, error)
+            }
+            ReplayError::InputsOutOfOrder(tick) => {
+                write!(f, "inputs out of order at tick {}", tick)
+            }
+            ReplayError::InputAfterLength(tick) => {
+                write!(f, "input at tick {} after replay length", tick)
+            }
+        }
+    }
+}
+
+/// Feeds a recorded `Replay` back one tick at a time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    tick: u32,
+    cursor: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            tick: 0,
+            cursor: 0,
+        }
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.replay.length
+    }
+
+    /// Returns the input for the next tick, or `None` once the recording ends.
+    pub fn next_input(&mut self) -> Option<Input> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let direction = match self.replay.inputs.get(self.cursor) {
+            Some(&(tick, direction)) if tick == self.tick => {
+                self.cursor += 1;
+                Some(direction)
+            }
+            _ => None,
+        };
+        self.tick += 1;
+        Some(Input { direction })
+    }
+}
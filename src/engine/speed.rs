@@ -0,0 +1,30 @@
+use super::LevelSpec;
+use crate::maze::Maze;
+use crate::models::{Ghost, GhostMode};
+
+/// Eyes hurry home at top speed whatever the level.
+const EATEN_GHOST_SPEED: u32 = 100;
+
+/// Pac-Man's speed this tick, as a percentage of top speed.
+pub fn pacman_speed(spec: &LevelSpec, frightened: bool) -> u32 {
+    if frightened {
+        spec.pacman_frightened_speed
+    } else {
+        spec.pacman_speed
+    }
+}
+
+/// A ghost's speed this tick, as a percentage of top speed. Tunnels slow
+/// every ghost except eyes, which ignore everything, and Cruise Elroy only
+/// speeds Blinky up while he is hunting.
+pub fn ghost_speed(ghost: &Ghost, spec: &LevelSpec, maze: &Maze) -> u32 {
+    match ghost.mode {
+        GhostMode::Eaten => EATEN_GHOST_SPEED,
+        _ if maze.is_tunnel(&ghost.position) => spec.ghost_tunnel_speed,
+        GhostMode::Frightened => spec.ghost_frightened_speed,
+        GhostMode::Scatter | GhostMode::Chase if ghost.elroy > 0 => {
+            spec.elroy_speeds[usize::from(ghost.elroy.min(2)) - 1]
+        }
+        _ => spec.ghost_speed,
+    }
+}
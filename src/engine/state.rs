@@ -0,0 +1,545 @@
+use super::{
+    ghost_speed, ghosts_met, pacman_speed, subtiles_per_tick, ExtraLifeRule, GameEvent, GameRng,
+    GhostHouse, LevelSpec, ModeClock, Timer, TimerWheel,
+};
+use crate::brain::{GhostBrains, GhostView};
+use crate::constants::game::{
+    ticks_from_ms, DEATH_DELAY_MS, FRUIT_DOT_THRESHOLDS, FRUIT_MS, LEVEL_COMPLETE_DELAY_MS,
+    RESPAWN_DELAY_MS, SCORE_POPUP_MS, STARTING_LIVES,
+};
+use crate::game_logic;
+use crate::maze::{maze_index, Maze, Walker};
+use crate::models::{Direction, Fruit, Ghost, GhostId, GhostMode, Motion, Position};
+use crate::pathfinding::{DistanceTable, GhostAi};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamePhase {
+    NotStarted,
+    Playing,
+    Dying,
+    Respawning,
+    LevelComplete,
+    GameOver,
+}
+
+/// Player input sampled for one tick. `direction` is `None` when no key was
+/// pressed since the previous tick. A pressed direction is queued and taken as
+/// soon as that way is open; until then Pac-Man keeps his heading.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Input {
+    pub direction: Option<Direction>,
+}
+
+/// Points floating above the tile where a ghost was eaten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScorePopup {
+    pub position: Position,
+    pub points: i32,
+    pub ticks_left: u32,
+}
+
+/// A bonus fruit waiting on the board until Pac-Man reaches it or it vanishes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BonusFruit {
+    pub fruit: Fruit,
+    pub position: Position,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct GameState {
+    pub maze: Maze,
+    pub pacman_pos: Position,
+    pub direction: Direction,
+    pub queued_direction: Option<Direction>,
+    pub pacman_motion: Motion,
+    pub ghosts: Vec<Ghost>,
+    pub popups: Vec<ScorePopup>,
+    pub fruit: Option<BonusFruit>,
+    pub collected_fruit: Vec<Fruit>,
+    pub score: i32,
+    pub lives: i32,
+    pub phase: GamePhase,
+    pub level: u32,
+    pub tick: u32,
+    pub extra_life: ExtraLifeRule,
+    pub ghost_ai: GhostAi,
+    /// Custom AI registered per ghost. Replays record the names the brains
+    /// were picked by and rebuild them on playback.
+    pub brains: GhostBrains,
+    layouts: Vec<Maze>,
+    /// Distances between tiles of the current maze, built when first needed.
+    distances: Option<Rc<DistanceTable>>,
+    rng: GameRng,
+    mode_clock: ModeClock,
+    ghost_house: GhostHouse,
+    timers: TimerWheel,
+    /// The timers a pause is holding, `None` while the game runs.
+    paused: Option<Vec<Timer>>,
+    ghost_combo: u32,
+    dots_eaten: u32,
+    elroy_suspended: bool,
+}
+
+impl GameState {
+    pub fn new(seed: u64) -> Self {
+        Self::from_maze(Maze::classic(), seed)
+    }
+
+    pub fn from_maze(layout: Maze, seed: u64) -> Self {
+        Self::from_mazes(vec![layout], seed)
+    }
+
+    /// A game over an ordered list of mazes, one per level, going back to the
+    /// first after the last.
+    pub fn from_mazes(layouts: Vec<Maze>, seed: u64) -> Self {
+        assert!(!layouts.is_empty(), "a game needs at least one maze");
+        let layout = layouts[0].clone();
+        Self {
+            maze: layout.clone(),
+            pacman_pos: layout.pacman_spawn.clone(),
+            direction: Direction::None,
+            queued_direction: None,
+            pacman_motion: Motion::default(),
+            ghosts: Ghost::initialize_ghosts(&layout),
+            popups: Vec::new(),
+            fruit: None,
+            collected_fruit: Vec::new(),
+            score: 0,
+            lives: STARTING_LIVES,
+            phase: GamePhase::NotStarted,
+            level: 1,
+            tick: 0,
+            extra_life: ExtraLifeRule::default(),
+            ghost_ai: GhostAi::default(),
+            brains: GhostBrains::default(),
+            rng: GameRng::new(seed),
+            mode_clock: ModeClock::new(1),
+            ghost_house: GhostHouse::new(1),
+            timers: TimerWheel::default(),
+            paused: None,
+            ghost_combo: 0,
+            dots_eaten: 0,
+            elroy_suspended: false,
+            layouts,
+            distances: None,
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.phase == GamePhase::NotStarted {
+            self.phase = GamePhase::Playing;
+        }
+    }
+
+    pub fn restart(&mut self, seed: u64) {
+        let (extra_life, ghost_ai) = (self.extra_life, self.ghost_ai);
+        let brains = self.brains.clone();
+        *self = Self::from_mazes(self.layouts.clone(), seed);
+        self.extra_life = extra_life;
+        self.ghost_ai = ghost_ai;
+        self.brains = brains;
+        self.phase = GamePhase::Playing;
+    }
+
+    /// Freezes the game, timers included, until `resume`. Only a game in
+    /// progress can be paused.
+    pub fn pause(&mut self) {
+        if self.is_started() && !self.is_over() && self.paused.is_none() {
+            let held = self.timers.running();
+            for &timer in &held {
+                self.timers.pause(timer);
+            }
+            self.paused = Some(held);
+        }
+    }
+
+    /// Picks up where `pause` left off. Timers the game had already put on
+    /// hold itself stay held.
+    pub fn resume(&mut self) {
+        for timer in self.paused.take().unwrap_or_default() {
+            self.timers.resume(timer);
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.phase != GamePhase::NotStarted
+    }
+
+    pub fn is_invincible(&self) -> bool {
+        self.timers.remaining(Timer::PowerPellet) > 0
+    }
+
+    pub fn is_dying(&self) -> bool {
+        matches!(
+            self.phase,
+            GamePhase::Dying | GamePhase::Respawning | GamePhase::GameOver
+        )
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.phase == GamePhase::GameOver
+    }
+
+    /// The current level's maze as loaded, before any dots were eaten.
+    pub fn layout(&self) -> &Maze {
+        &self.layouts[maze_index(self.level, self.layouts.len())]
+    }
+
+    pub fn layouts(&self) -> &[Maze] {
+        &self.layouts
+    }
+
+    /// Difficulty settings for the level being played.
+    pub fn level_spec(&self) -> LevelSpec {
+        LevelSpec::for_level(self.level)
+    }
+
+    /// Advances the game by one tick and reports what happened. Input is
+    /// ignored until the game has started so that recordings begin at `start`,
+    /// and while it is paused.
+    pub fn step(&mut self, input: Input) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if !self.is_started() || self.is_paused() {
+            return events;
+        }
+
+        if input.direction.is_some() {
+            self.queued_direction = input.direction;
+        }
+
+        let expired = self.timers.advance();
+        let phase_over = expired.contains(&Timer::Phase);
+        match self.phase {
+            GamePhase::Playing => self.play(&expired, &mut events),
+            GamePhase::Dying => {
+                if phase_over {
+                    if self.lives > 0 {
+                        self.phase = GamePhase::Respawning;
+                        self.timers
+                            .start(Timer::Phase, ticks_from_ms(RESPAWN_DELAY_MS));
+                    } else {
+                        self.phase = GamePhase::GameOver;
+                        events.push(GameEvent::GameOver);
+                    }
+                }
+            }
+            GamePhase::Respawning => {
+                if phase_over {
+                    self.reset_positions();
+                    self.phase = GamePhase::Playing;
+                    events.push(GameEvent::Respawned);
+                }
+            }
+            GamePhase::LevelComplete => {
+                if phase_over {
+                    self.next_level();
+                }
+            }
+            GamePhase::NotStarted | GamePhase::GameOver => {}
+        }
+
+        events
+    }
+
+    fn play(&mut self, expired: &[Timer], events: &mut Vec<GameEvent>) {
+        let score_before = self.score;
+        self.play_tick(expired, events);
+        for _ in 0..self.extra_life.lives_earned(score_before, self.score) {
+            self.lives += 1;
+            events.push(GameEvent::ExtraLife);
+        }
+    }
+
+    fn play_tick(&mut self, expired: &[Timer], events: &mut Vec<GameEvent>) {
+        self.tick += 1;
+        self.update_ghost_modes(expired, events);
+        self.update_popups();
+        self.update_fruit(expired, events);
+
+        let spec = self.level_spec();
+        let ghost_speeds: Vec<u32> = self
+            .ghosts
+            .iter()
+            .map(|ghost| subtiles_per_tick(ghost_speed(ghost, &spec, &self.maze)))
+            .collect();
+        let distances = self.distance_table();
+        let ghosts = self.ghosts.clone();
+        let view = GhostView {
+            maze: &self.maze,
+            pacman_pos: &self.pacman_pos,
+            pacman_direction: self.direction,
+            ghosts: &ghosts,
+            distances: distances.as_deref(),
+        };
+        let ghost_trails = game_logic::move_ghosts(
+            &mut self.ghosts,
+            &view,
+            &self.brains,
+            &ghost_speeds,
+            &mut self.rng,
+        );
+
+        if game_logic::check_game_complete(&self.maze) {
+            self.end_play(GamePhase::LevelComplete, LEVEL_COMPLETE_DELAY_MS);
+            events.push(GameEvent::LevelCleared(self.level));
+            return;
+        }
+
+        let mut pacman_trail = vec![self.pacman_pos.clone()];
+        for _ in 0..self
+            .pacman_motion
+            .advance(subtiles_per_tick(pacman_speed(&spec, self.is_invincible())))
+        {
+            if !self.move_pacman(events) {
+                self.pacman_motion.stop();
+                break;
+            }
+            pacman_trail.push(self.pacman_pos.clone());
+        }
+
+        // Collisions are settled once everyone has moved, over the whole
+        // tick: frightened ghosts Pac-Man met are eaten first, then any
+        // dangerous one catches him.
+        let met = ghosts_met(&pacman_trail, &ghost_trails);
+        self.eat_ghosts(&met, events);
+        if game_logic::check_ghost_collision(&met, &self.ghosts) {
+            self.lives -= 1;
+            self.ghost_house.on_life_lost();
+            self.elroy_suspended = true;
+            self.end_play(GamePhase::Dying, DEATH_DELAY_MS);
+            events.push(GameEvent::PacmanCaught);
+            return;
+        }
+        self.eat_fruit(events);
+    }
+
+    /// Leaves play for `phase`, which lasts `delay_ms`. The frightened and
+    /// fruit timers hold still meanwhile.
+    fn end_play(&mut self, phase: GamePhase, delay_ms: u32) {
+        self.phase = phase;
+        self.timers.start(Timer::Phase, ticks_from_ms(delay_ms));
+        self.timers.pause(Timer::PowerPellet);
+        self.timers.pause(Timer::Fruit);
+    }
+
+    /// Takes one tile step, returning `false` when Pac-Man is blocked.
+    fn move_pacman(&mut self, events: &mut Vec<GameEvent>) -> bool {
+        if let Some(queued) = self
+            .queued_direction
+            .filter(|&queued| game_logic::pacman_can_move(queued, &self.pacman_pos, &self.maze))
+        {
+            self.direction = queued;
+            self.queued_direction = None;
+        }
+
+        let score_before = self.score;
+        let Some((next_pos, power_pellet_eaten)) = game_logic::calculate_next_position(
+            &self.direction,
+            &self.pacman_pos,
+            &mut self.maze,
+            &mut self.score,
+        ) else {
+            return false;
+        };
+
+        if power_pellet_eaten {
+            self.frighten_ghosts();
+            events.push(GameEvent::PowerPelletEaten(next_pos.clone()));
+        } else if self.score > score_before {
+            events.push(GameEvent::DotEaten(next_pos.clone()));
+        }
+        if self.score > score_before {
+            self.ghost_house.on_dot_eaten(&self.ghosts);
+            self.on_dot_eaten(events);
+        }
+        self.pacman_motion.wrapped = next_pos.x.abs_diff(self.pacman_pos.x) > 1;
+        self.pacman_pos = next_pos;
+        true
+    }
+
+    /// Runs the frightened timer or, when no power pellet is active, the
+    /// scatter/chase clock, releases ghosts from the house and hands ghosts
+    /// that made it out of the door over to the schedule.
+    fn update_ghost_modes(&mut self, expired: &[Timer], events: &mut Vec<GameEvent>) {
+        if expired.contains(&Timer::PowerPellet) {
+            let mode = self.mode_clock.mode();
+            for ghost in self.ghosts.iter_mut() {
+                if ghost.mode == GhostMode::Frightened {
+                    ghost.set_mode(mode);
+                }
+            }
+            events.push(GameEvent::PowerPelletExpired);
+        } else if !self.is_invincible() {
+            if let Some(mode) = self.mode_clock.tick() {
+                for ghost in self.ghosts.iter_mut() {
+                    if ghost.is_dangerous() {
+                        ghost.set_mode(mode);
+                    }
+                }
+            }
+        }
+
+        if let Some(id) = self.ghost_house.tick(&self.ghosts) {
+            if let Some(ghost) = self.ghosts.iter_mut().find(|ghost| ghost.id == id) {
+                ghost.set_mode(GhostMode::Leaving);
+            }
+        }
+
+        let mode = self.mode_clock.mode();
+        for ghost in self.ghosts.iter_mut() {
+            if ghost.mode == GhostMode::Leaving && ghost.position == self.maze.house_exit {
+                ghost.set_mode(mode);
+            }
+        }
+
+        self.update_elroy();
+    }
+
+    /// Speeds Blinky up as the dots run out. After a life is lost he stays
+    /// normal until Clyde is out of the house again.
+    fn update_elroy(&mut self) {
+        let clyde_waiting = self.ghosts.iter().any(|ghost| {
+            ghost.id == GhostId::Clyde
+                && matches!(ghost.mode, GhostMode::InHouse | GhostMode::Leaving)
+        });
+        if !clyde_waiting {
+            self.elroy_suspended = false;
+        }
+
+        let stage = if self.elroy_suspended {
+            0
+        } else {
+            self.level_spec().elroy_stage(self.maze.remaining_dots())
+        };
+        for ghost in self.ghosts.iter_mut() {
+            if ghost.id == GhostId::Blinky {
+                ghost.elroy = stage;
+            }
+        }
+    }
+
+    fn eat_ghosts(&mut self, met: &[bool], events: &mut Vec<GameEvent>) {
+        for ghost in game_logic::eat_frightened_ghosts(met, &mut self.ghosts) {
+            let points = game_logic::ghost_points(self.ghost_combo);
+            self.ghost_combo += 1;
+            self.score += points;
+            self.popups.push(ScorePopup {
+                position: self.pacman_pos.clone(),
+                points,
+                ticks_left: ticks_from_ms(SCORE_POPUP_MS),
+            });
+            events.push(GameEvent::GhostEaten {
+                ghost,
+                position: self.pacman_pos.clone(),
+                points,
+            });
+        }
+    }
+
+    fn on_dot_eaten(&mut self, events: &mut Vec<GameEvent>) {
+        self.dots_eaten += 1;
+        if FRUIT_DOT_THRESHOLDS.contains(&self.dots_eaten) {
+            let fruit = self.level_spec().fruit;
+            self.fruit = Some(BonusFruit {
+                fruit,
+                position: self.layout().fruit_spawn.clone(),
+            });
+            self.timers.start(Timer::Fruit, ticks_from_ms(FRUIT_MS));
+            events.push(GameEvent::FruitSpawned(fruit));
+        }
+    }
+
+    fn update_fruit(&mut self, expired: &[Timer], events: &mut Vec<GameEvent>) {
+        if expired.contains(&Timer::Fruit) && self.fruit.take().is_some() {
+            events.push(GameEvent::FruitExpired);
+        }
+    }
+
+    fn eat_fruit(&mut self, events: &mut Vec<GameEvent>) {
+        let Some(bonus) = self
+            .fruit
+            .take_if(|bonus| bonus.position == self.pacman_pos)
+        else {
+            return;
+        };
+        self.timers.cancel(Timer::Fruit);
+        let points = bonus.fruit.points();
+        self.score += points;
+        self.collected_fruit.push(bonus.fruit);
+        self.popups.push(ScorePopup {
+            position: bonus.position,
+            points,
+            ticks_left: ticks_from_ms(SCORE_POPUP_MS),
+        });
+        events.push(GameEvent::FruitEaten {
+            fruit: bonus.fruit,
+            points,
+        });
+    }
+
+    fn update_popups(&mut self) {
+        for popup in self.popups.iter_mut() {
+            popup.ticks_left = popup.ticks_left.saturating_sub(1);
+        }
+        self.popups.retain(|popup| popup.ticks_left > 0);
+    }
+
+    fn frighten_ghosts(&mut self) {
+        self.timers
+            .start(Timer::PowerPellet, self.level_spec().frightened_ticks());
+        self.ghost_combo = 0;
+        for ghost in self.ghosts.iter_mut() {
+            if ghost.is_dangerous() {
+                ghost.set_mode(GhostMode::Frightened);
+            }
+        }
+    }
+
+    /// The current maze's distance table when ghosts steer by shortest path.
+    fn distance_table(&mut self) -> Option<Rc<DistanceTable>> {
+        if self.ghost_ai != GhostAi::ShortestPath {
+            return None;
+        }
+        if self.distances.is_none() {
+            let table = DistanceTable::new(self.layout(), Walker::Ghost);
+            self.distances = Some(Rc::new(table));
+        }
+        self.distances.clone()
+    }
+
+    /// Refills the maze for the next level; score and lives carry over.
+    fn next_level(&mut self) {
+        self.level += 1;
+        self.maze = self.layout().clone();
+        self.distances = None;
+        self.dots_eaten = 0;
+        self.elroy_suspended = false;
+        self.ghost_house = GhostHouse::new(self.level);
+        self.reset_positions();
+        self.phase = GamePhase::Playing;
+    }
+
+    /// Puts everyone back on their spawns. A turn queued during the
+    /// countdown before this is kept, so the player's first input after a
+    /// death or between levels isn't lost.
+    fn reset_positions(&mut self) {
+        self.pacman_pos = self.layout().pacman_spawn.clone();
+        self.ghosts = Ghost::initialize_ghosts(self.layout());
+        self.direction = Direction::None;
+        self.pacman_motion = Motion::default();
+        self.timers.cancel(Timer::PowerPellet);
+        self.popups.clear();
+        self.fruit = None;
+        self.timers.cancel(Timer::Fruit);
+        self.mode_clock = ModeClock::new(self.level);
+    }
+}
@@ -0,0 +1,86 @@
+//! Countdowns measured in game ticks. Each timer has its own slot, so starting
+//! one again replaces the old countdown and nothing fires for a countdown that
+//! was cancelled or belongs to a game that has since been restarted.
+
+/// The engine's countdowns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timer {
+    /// Delay before the phase after dying, respawning or clearing a level.
+    Phase,
+    /// How long ghosts stay frightened after a power pellet.
+    PowerPellet,
+    /// How long a bonus fruit stays on the board.
+    Fruit,
+}
+
+const TIMER_COUNT: usize = 3;
+const TIMERS: [Timer; TIMER_COUNT] = [Timer::Phase, Timer::PowerPellet, Timer::Fruit];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Slot {
+    remaining: u32,
+    paused: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimerWheel {
+    slots: [Slot; TIMER_COUNT],
+}
+
+impl TimerWheel {
+    /// Runs `timer` for `ticks` ticks, replacing any countdown already
+    /// running. Starting it for zero ticks stops it.
+    pub fn start(&mut self, timer: Timer, ticks: u32) {
+        self.slots[timer as usize] = Slot {
+            remaining: ticks,
+            paused: false,
+        };
+    }
+
+    pub fn cancel(&mut self, timer: Timer) {
+        self.slots[timer as usize] = Slot::default();
+    }
+
+    /// Holds `timer` where it is until it is resumed, started again or
+    /// cancelled.
+    pub fn pause(&mut self, timer: Timer) {
+        self.slots[timer as usize].paused = true;
+    }
+
+    /// Lets a paused `timer` carry on from where it was held.
+    pub fn resume(&mut self, timer: Timer) {
+        self.slots[timer as usize].paused = false;
+    }
+
+    /// Timers counting down right now, not counting paused ones.
+    pub fn running(&self) -> Vec<Timer> {
+        TIMERS
+            .into_iter()
+            .filter(|&timer| {
+                let slot = self.slots[timer as usize];
+                !slot.paused && slot.remaining > 0
+            })
+            .collect()
+    }
+
+    /// Ticks left on `timer`, paused or not; zero when it isn't set.
+    pub fn remaining(&self, timer: Timer) -> u32 {
+        self.slots[timer as usize].remaining
+    }
+
+    /// Counts every running timer down by one tick and returns those that ran
+    /// out on this tick.
+    pub fn advance(&mut self) -> Vec<Timer> {
+        TIMERS
+            .into_iter()
+            .filter(|&timer| {
+                let slot = &mut self.slots[timer as usize];
+                if slot.paused || slot.remaining == 0 {
+                    return false;
+                }
+                slot.remaining -= 1;
+                slot.remaining == 0
+            })
+            .collect()
+    }
+}
@@ -1,158 +1,387 @@
-use crate::models::{Direction, Ghost, Position};
-use rand::Rng;
-use yew::UseStateHandle;
+use crate::brain::{GhostBrain, GhostBrains, GhostView};
+use crate::constants::game::{DOT_POINTS, FRIGHTENED_WANDER_CHANCE, POWER_PELLET_POINTS};
+use crate::maze::{Maze, Tile, Walker};
+use crate::models::{Direction, Ghost, GhostId, GhostMode, Motion, Position};
+use crate::pathfinding::{a_star, DistanceTable};
+use rand::{Rng, RngCore};
 
-pub fn find_ghost_move(
+/// Order the arcade breaks ties in when two moves are equally good.
+const GHOST_TIE_BREAK: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
+
+pub fn find_ghost_move(ghost: &Ghost, target: &Position, maze: &Maze) -> Option<Position> {
+    find_best_move(&get_ghost_moves(ghost, maze), target, true, maze)
+}
+
+/// Neighbouring tile that starts a shortest path to `target`, or `None` when
+/// the target is off the ghosts' graph (a wall, say) or can't be reached.
+pub fn find_shortest_path_move(
     ghost: &Ghost,
-    pacman_pos: &Position,
-    maze: &[Vec<u8>],
-    aggressive: bool,
+    target: &Position,
+    maze: &Maze,
+    distances: &DistanceTable,
 ) -> Option<Position> {
-    let possible_moves = get_valid_ghost_moves(&ghost.position, maze);
-    if possible_moves.is_empty() {
-        return None;
-    }
+    get_ghost_moves(ghost, maze)
+        .into_iter()
+        .filter_map(|pos| {
+            distances
+                .distance(&pos, target)
+                .map(|distance| (distance, pos))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, pos)| pos)
+}
+
+/// Steers towards `target` by shortest path when given a distance table,
+/// falling back to the arcade's straight-line rule for targets the table
+/// can't reach.
+pub fn find_target_move(
+    ghost: &Ghost,
+    target: &Position,
+    maze: &Maze,
+    distances: Option<&DistanceTable>,
+) -> Option<Position> {
+    distances
+        .and_then(|distances| find_shortest_path_move(ghost, target, maze, distances))
+        .or_else(|| find_ghost_move(ghost, target, maze))
+}
 
-    let mut rng = rand::thread_rng();
-    let make_best_move = if aggressive {
-        true
+/// Pinky's chase target: four tiles ahead of Pac-Man.
+pub fn ambush_target(pacman_pos: &Position, pacman_direction: Direction, maze: &Maze) -> Position {
+    let (x, y) = tiles_ahead(pacman_pos, pacman_direction, 4);
+    clamp_to_maze(x, y, maze)
+}
+
+/// Inky's chase target: the vector from Blinky to the tile two ahead of
+/// Pac-Man, doubled.
+pub fn flank_target(
+    pacman_pos: &Position,
+    pacman_direction: Direction,
+    blinky_pos: &Position,
+    maze: &Maze,
+) -> Position {
+    let (pivot_x, pivot_y) = tiles_ahead(pacman_pos, pacman_direction, 2);
+    clamp_to_maze(
+        2 * pivot_x - blinky_pos.x as i32,
+        2 * pivot_y - blinky_pos.y as i32,
+        maze,
+    )
+}
+
+/// Clyde's chase target: Pac-Man while he is at least eight tiles away,
+/// otherwise his own scatter corner.
+pub fn shy_target(ghost: &Ghost, pacman_pos: &Position) -> Position {
+    let dx = ghost.position.x as i32 - pacman_pos.x as i32;
+    let dy = ghost.position.y as i32 - pacman_pos.y as i32;
+    if dx * dx + dy * dy >= 8 * 8 {
+        pacman_pos.clone()
     } else {
-        rng.gen_bool(0.7) 
+        ghost.scatter_target.clone()
+    }
+}
+
+fn tiles_ahead(pos: &Position, direction: Direction, tiles: i32) -> (i32, i32) {
+    let (dx, dy) = match direction {
+        Direction::Up => (0, -1),
+        Direction::Down => (0, 1),
+        Direction::Left => (-1, 0),
+        Direction::Right => (1, 0),
+        Direction::None => (0, 0),
     };
+    (pos.x as i32 + dx * tiles, pos.y as i32 + dy * tiles)
+}
+
+fn clamp_to_maze(x: i32, y: i32, maze: &Maze) -> Position {
+    Position {
+        x: x.clamp(0, maze.width() as i32 - 1) as usize,
+        y: y.clamp(0, maze.height() as i32 - 1) as usize,
+    }
+}
+
+/// Frightened ghosts run from Pac-Man, with the odd random turn so they
+/// don't all flee along the same line.
+pub fn find_flee_move(
+    ghost: &Ghost,
+    pacman_pos: &Position,
+    maze: &Maze,
+    rng: &mut dyn RngCore,
+) -> Option<Position> {
+    let possible_moves = get_ghost_moves(ghost, maze);
+    if possible_moves.len() <= 1 {
+        return possible_moves.into_iter().next();
+    }
 
-    find_best_move(&possible_moves, pacman_pos, make_best_move)
+    if rng.gen_bool(FRIGHTENED_WANDER_CHANCE) {
+        return Some(possible_moves[rng.gen_range(0..possible_moves.len())].clone());
+    }
+    find_best_move(&possible_moves, pacman_pos, false, maze)
 }
 
-pub fn get_valid_ghost_moves(position: &Position, maze: &[Vec<u8>]) -> Vec<Position> {
-    let mut moves = Vec::new();
-    let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)]; 
+/// First step of a shortest path from `from` to `to`, found with A*.
+/// Only ghosts entering or leaving the house use this, so the path may cross
+/// the ghost door, and eyes never get stuck behind a wall on the way home.
+pub fn find_path_move(from: &Position, to: &Position, maze: &Maze) -> Option<Position> {
+    a_star(maze, from, to, Walker::HouseGhost)?
+        .into_iter()
+        .next()
+}
 
-    for (dx, dy) in directions.iter() {
-        let new_x = position.x as i32 + dx;
-        let new_y = position.y as i32 + dy;
+fn find_reverse_move(ghost: &Ghost, maze: &Maze) -> Option<Position> {
+    let reverse = maze.neighbor(&ghost.position, ghost.direction.opposite())?;
+    get_valid_ghost_moves(&ghost.position, maze)
+        .into_iter()
+        .find(|pos| *pos == reverse)
+}
 
-        if new_x >= 0
-            && new_y >= 0
-            && new_x < maze[0].len() as i32
-            && new_y < maze.len() as i32
-            && maze[new_y as usize][new_x as usize] != 1
-        {
-            moves.push(Position {
-                x: new_x as usize,
-                y: new_y as usize,
-            });
+pub fn direction_between(from: &Position, to: &Position) -> Direction {
+    if to.y < from.y {
+        Direction::Up
+    } else if to.y > from.y {
+        Direction::Down
+    } else if to.x != from.x {
+        // A jump of more than one column is a trip through a tunnel.
+        let wrapped = to.x.abs_diff(from.x) > 1;
+        if (to.x < from.x) != wrapped {
+            Direction::Left
+        } else {
+            Direction::Right
         }
+    } else {
+        Direction::None
+    }
+}
+
+pub fn get_valid_ghost_moves(position: &Position, maze: &Maze) -> Vec<Position> {
+    maze.neighbors(position, Walker::Ghost)
+}
+
+/// Tiles a ghost may move to next, in tie-break order. Ghosts never turn
+/// back the way they came unless they hit a dead end, so away from junctions
+/// there is only one way to go and no decision to make.
+pub fn get_ghost_moves(ghost: &Ghost, maze: &Maze) -> Vec<Position> {
+    let ahead: Vec<Position> = GHOST_TIE_BREAK
+        .into_iter()
+        .filter(|&direction| direction != ghost.direction.opposite())
+        .filter_map(|direction| maze.step(&ghost.position, direction, Walker::Ghost))
+        .collect();
+    if ahead.is_empty() {
+        find_reverse_move(ghost, maze).into_iter().collect()
+    } else {
+        ahead
     }
-    moves
 }
 
 fn find_best_move(
     possible_moves: &[Position],
-    pacman_pos: &Position,
+    target: &Position,
     make_best_move: bool,
+    maze: &Maze,
 ) -> Option<Position> {
+    let score = |pos: &Position| {
+        let distance = target_distance(pos, target, maze);
+        if make_best_move {
+            distance
+        } else {
+            -distance
+        }
+    };
     possible_moves
         .iter()
-        .min_by_key(|pos| {
-            let dx = pos.x as i32 - pacman_pos.x as i32;
-            let dy = pos.y as i32 - pacman_pos.y as i32;
-            let distance = dx * dx + dy * dy;
-            if make_best_move {
-                distance
-            } else {
-                -distance
-            }
-        })
+        .min_by(|a, b| score(a).total_cmp(&score(b)))
         .cloned()
 }
 
+/// Straight-line distance from `pos` to `target`, or the shorter way round
+/// through a tunnel if the maze has one, so ghosts chase through the wrap.
+fn target_distance(pos: &Position, target: &Position, maze: &Maze) -> f64 {
+    let last_x = maze.width() - 1;
+    maze.tunnel_rows().fold(euclidean(pos, target), |best, y| {
+        let left = Position { x: 0, y };
+        let right = Position { x: last_x, y };
+        let via_left = euclidean(pos, &left) + 1.0 + euclidean(&right, target);
+        let via_right = euclidean(pos, &right) + 1.0 + euclidean(&left, target);
+        best.min(via_left).min(via_right)
+    })
+}
+
+fn euclidean(a: &Position, b: &Position) -> f64 {
+    let dx = a.x as f64 - b.x as f64;
+    let dy = a.y as f64 - b.y as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Tile Pac-Man would reach heading `direction` from `pos`, if nothing blocks him.
+fn pacman_step(direction: Direction, pos: &Position, maze: &Maze) -> Option<Position> {
+    maze.step(pos, direction, Walker::Pacman)
+}
+
+pub fn pacman_can_move(direction: Direction, pos: &Position, maze: &Maze) -> bool {
+    pacman_step(direction, pos, maze).is_some()
+}
+
 pub fn calculate_next_position(
     current_direction: &Direction,
     current_pos: &Position,
-    maze: &mut [Vec<u8>],
+    maze: &mut Maze,
     score: &mut i32,
 ) -> Option<(Position, bool)> {
-    let mut new_pos = current_pos.clone();
-
-    let can_move = match current_direction {
-        Direction::Up => new_pos.y > 0 && maze[new_pos.y - 1][new_pos.x] != 1,
-        Direction::Down => new_pos.y < maze.len() - 1 && maze[new_pos.y + 1][new_pos.x] != 1,
-        Direction::Left => new_pos.x > 0 && maze[new_pos.y][new_pos.x - 1] != 1,
-        Direction::Right => new_pos.x < maze[0].len() - 1 && maze[new_pos.y][new_pos.x + 1] != 1,
-        Direction::None => false,
-    };
+    let new_pos = pacman_step(*current_direction, current_pos, maze)?;
 
-    if can_move {
-        match current_direction {
-            Direction::Up => new_pos.y -= 1,
-            Direction::Down => new_pos.y += 1,
-            Direction::Left => new_pos.x -= 1,
-            Direction::Right => new_pos.x += 1,
-            Direction::None => (),
-        }
-
-        let power_pellet_eaten = update_score(&new_pos, maze, score);
-        Some((new_pos, power_pellet_eaten))
-    } else {
-        None
-    }
+    let power_pellet_eaten = update_score(&new_pos, maze, score);
+    Some((new_pos, power_pellet_eaten))
 }
 
-pub fn update_score(pos: &Position, maze: &mut [Vec<u8>], score: &mut i32) -> bool {
-    match maze[pos.y][pos.x] {
-        2 => {
-            *score += 10;
-            maze[pos.y][pos.x] = 0;
+pub fn update_score(pos: &Position, maze: &mut Maze, score: &mut i32) -> bool {
+    match maze.eat(pos) {
+        Some(Tile::PowerPellet) => {
+            *score += POWER_PELLET_POINTS;
+            true
+        }
+        Some(_) => {
+            *score += DOT_POINTS;
             false
         }
-        3 => {
-            *score += 50;
-            maze[pos.y][pos.x] = 0;
-            true
+        None => false,
+    }
+}
+
+/// Whether any dangerous ghost among those flagged in `met` reached Pac-Man.
+pub fn check_ghost_collision(met: &[bool], ghosts: &[Ghost]) -> bool {
+    ghosts
+        .iter()
+        .zip(met)
+        .any(|(ghost, &met)| met && ghost.is_dangerous())
+}
+
+/// Sends every frightened ghost flagged in `met` home as eyes and returns
+/// which ghosts were caught.
+pub fn eat_frightened_ghosts(met: &[bool], ghosts: &mut [Ghost]) -> Vec<GhostId> {
+    let mut eaten = Vec::new();
+    for (ghost, &met) in ghosts.iter_mut().zip(met) {
+        if met && ghost.mode == GhostMode::Frightened {
+            ghost.set_mode(GhostMode::Eaten);
+            eaten.push(ghost.id);
         }
-        _ => false,
     }
+    eaten
 }
 
-pub fn check_ghost_collision(
-    pacman_pos: &Position,
-    ghosts: &[Ghost],
-    is_dying: UseStateHandle<bool>,
-    lives: UseStateHandle<i32>,
-    invincibility: i32,
-) -> bool {
-    if invincibility <= 0 {
-        for ghost in ghosts {
-            if ghost.position == *pacman_pos {
-                is_dying.set(true);
-                lives.set(*lives - 1);
-                return true;
+/// Points for the `combo`-th ghost (zero-based) eaten on one power pellet:
+/// 200, 400, 800, then 1600 for every ghost after that.
+pub fn ghost_points(combo: u32) -> i32 {
+    200 << combo.min(3)
+}
+
+/// Moves each ghost by its entry in `speeds`, in sub-tiles, taking a tile
+/// step for each whole tile covered. Outside the ghost house each ghost goes
+/// where its brain in `brains` points, as long as that is a legal move.
+/// Returns each ghost's trail: its starting tile and every tile it entered.
+pub fn move_ghosts(
+    ghosts: &mut [Ghost],
+    view: &GhostView,
+    brains: &GhostBrains,
+    speeds: &[u32],
+    rng: &mut impl RngCore,
+) -> Vec<Vec<Position>> {
+    let mut trails = Vec::with_capacity(ghosts.len());
+    for (ghost, &speed) in ghosts.iter_mut().zip(speeds) {
+        let brain = brains.get(ghost.id);
+        let mut trail = vec![ghost.position.clone()];
+        for _ in 0..ghost.motion.advance(speed) {
+            let from = ghost.position.clone();
+            step_ghost(ghost, view, brain, rng);
+            if ghost.position == from {
+                ghost.motion.stop();
+                break;
             }
+            ghost.motion.wrapped = ghost.position.x.abs_diff(from.x) > 1;
+            trail.push(ghost.position.clone());
         }
+        trails.push(trail);
     }
-    false
+    trails
 }
 
-pub fn move_ghosts(ghosts: &mut [Ghost], pacman_pos: &Position, maze: &[Vec<u8>]) {
-    let mut rng = rand::thread_rng();
-
-    for ghost in ghosts.iter_mut() {
-        let aggressive = match ghost.color {
-            "#FF0000" => true,
-            "#00FFFF" => rng.gen_bool(0.4),
-            "#FFB8FF" => rng.gen_bool(0.3),
-            "#FFB852" => false,
-            _ => rng.gen_bool(0.7),
-        };
+fn step_ghost(ghost: &mut Ghost, view: &GhostView, brain: &dyn GhostBrain, rng: &mut dyn RngCore) {
+    let maze = view.maze;
+    let reverse_move = if ghost.reverse_pending {
+        ghost.reverse_pending = false;
+        find_reverse_move(ghost, maze)
+    } else {
+        None
+    };
 
-        if let Some(new_pos) = find_ghost_move(ghost, pacman_pos, maze, aggressive) {
-            ghost.position = new_pos;
+    let next_move = reverse_move.or_else(|| match ghost.mode {
+        GhostMode::Chase | GhostMode::Scatter | GhostMode::Frightened => {
+            let wanted = brain.choose_direction(ghost, view, rng);
+            let moves = get_ghost_moves(ghost, maze);
+            let chosen = moves
+                .iter()
+                .position(|pos| direction_between(&ghost.position, pos) == wanted)
+                .unwrap_or(0);
+            moves.into_iter().nth(chosen)
         }
+        GhostMode::Eaten => find_path_move(&ghost.position, &ghost.home, maze),
+        GhostMode::Leaving => find_path_move(&ghost.position, &maze.house_exit, maze),
+        GhostMode::InHouse => None,
+    });
+
+    if let Some(new_pos) = next_move {
+        ghost.direction = direction_between(&ghost.position, &new_pos);
+        ghost.position = new_pos;
+    }
+
+    // Revived eyes head straight back out; the engine hands ghosts that
+    // reach the exit over to the scatter/chase schedule.
+    if ghost.mode == GhostMode::Eaten && ghost.position == ghost.home {
+        ghost.set_mode(GhostMode::Leaving);
     }
 }
 
-pub fn check_game_complete(maze: &[Vec<u8>]) -> bool {
-    !maze
-        .iter()
-        .any(|row| row.iter().any(|&cell| cell == 2 || cell == 3))
+/// Where to draw an actor standing on `pos` with `motion` of the way
+/// travelled towards the next tile along `direction`, in tile units. Actors
+/// facing a wall are drawn on their tile.
+fn glide_position(pos: &Position, direction: Direction, motion: &Motion, open: bool) -> (f64, f64) {
+    let (dx, dy) = if open {
+        let (x, y) = tiles_ahead(&Position { x: 0, y: 0 }, direction, 1);
+        (f64::from(x), f64::from(y))
+    } else {
+        (0.0, 0.0)
+    };
+    let fraction = motion.fraction();
+    (pos.x as f64 + dx * fraction, pos.y as f64 + dy * fraction)
+}
+
+pub fn pacman_render_position(
+    pos: &Position,
+    direction: Direction,
+    motion: &Motion,
+    maze: &Maze,
+) -> (f64, f64) {
+    glide_position(
+        pos,
+        direction,
+        motion,
+        pacman_can_move(direction, pos, maze),
+    )
+}
+
+pub fn ghost_render_position(ghost: &Ghost, maze: &Maze) -> (f64, f64) {
+    let walker = if matches!(ghost.mode, GhostMode::Eaten | GhostMode::Leaving) {
+        Walker::HouseGhost
+    } else {
+        Walker::Ghost
+    };
+    let open = maze
+        .step(&ghost.position, ghost.direction, walker)
+        .is_some();
+    glide_position(&ghost.position, ghost.direction, &ghost.motion, open)
+}
+
+pub fn check_game_complete(maze: &Maze) -> bool {
+    maze.remaining_dots() == 0
 }
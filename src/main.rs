@@ -1,9 +1,14 @@
 mod app;
+mod brain;
 mod components;
 mod constants;
 mod controls;
+mod engine;
 mod game_logic;
+mod maze;
 mod models;
+mod pathfinding;
+mod query;
 mod tests;
 
 use app::App;
@@ -0,0 +1,145 @@
+use super::{parse_maze, Maze, MazeError, Tile};
+
+const MIN_SIZE: usize = 3;
+const MAX_SIZE: usize = 60;
+
+/// What clicking a square in the editor paints there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Brush {
+    Tile(Tile),
+    PacmanSpawn,
+    GhostSpawn,
+}
+
+impl Brush {
+    pub fn symbol(self) -> char {
+        match self {
+            Brush::Tile(tile) => tile.symbol(),
+            Brush::PacmanSpawn => 'P',
+            Brush::GhostSpawn => 'G',
+        }
+    }
+}
+
+/// A maze being edited, kept as its ASCII symbols so that half-finished
+/// layouts with missing spawns or ragged imports can still be shown and
+/// fixed. Every change can be undone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeDraft {
+    rows: Vec<Vec<char>>,
+    undo: Vec<Vec<Vec<char>>>,
+    redo: Vec<Vec<Vec<char>>>,
+}
+
+impl MazeDraft {
+    /// Loads maze text as-is. Short rows are padded with walls so the grid
+    /// stays rectangular; nothing else is checked until `validate`.
+    pub fn from_text(text: &str) -> Self {
+        let mut rows: Vec<Vec<char>> = text
+            .trim_end_matches(['\r', '\n'])
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0).max(MIN_SIZE);
+        rows.resize(rows.len().max(MIN_SIZE), Vec::new());
+        for row in rows.iter_mut() {
+            row.resize(width, Tile::Wall.symbol());
+        }
+        Self {
+            rows,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn symbol(&self, x: usize, y: usize) -> char {
+        self.rows[y][x]
+    }
+
+    pub fn text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// Parses the draft, reporting everything that stops it being played.
+    pub fn validate(&self) -> Result<Maze, Vec<MazeError>> {
+        parse_maze(&self.text())
+    }
+
+    /// Replaces the whole maze with `text`, as an undoable change.
+    pub fn load(&mut self, text: &str) {
+        let loaded = Self::from_text(text);
+        if loaded.rows != self.rows {
+            self.checkpoint();
+            self.rows = loaded.rows;
+        }
+    }
+
+    /// Paints one square. There is only ever one Pac-Man spawn, so placing
+    /// it clears the old one.
+    pub fn paint(&mut self, x: usize, y: usize, brush: Brush) {
+        let symbol = brush.symbol();
+        if self.rows[y][x] == symbol {
+            return;
+        }
+        self.checkpoint();
+        if brush == Brush::PacmanSpawn {
+            for square in self.rows.iter_mut().flatten() {
+                if *square == symbol {
+                    *square = Tile::Empty.symbol();
+                }
+            }
+        }
+        self.rows[y][x] = symbol;
+    }
+
+    /// Grows or crops the maze from the bottom-right, filling new squares
+    /// with wall. Sizes are kept between 3 and 60 tiles.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let width = width.clamp(MIN_SIZE, MAX_SIZE);
+        let height = height.clamp(MIN_SIZE, MAX_SIZE);
+        if (width, height) == (self.width(), self.height()) {
+            return;
+        }
+        self.checkpoint();
+        self.rows.resize(height, vec![Tile::Wall.symbol(); width]);
+        for row in self.rows.iter_mut() {
+            row.resize(width, Tile::Wall.symbol());
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(rows) = self.undo.pop() {
+            self.redo.push(std::mem::replace(&mut self.rows, rows));
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(rows) = self.redo.pop() {
+            self.undo.push(std::mem::replace(&mut self.rows, rows));
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.undo.push(self.rows.clone());
+        self.redo.clear();
+    }
+}
@@ -0,0 +1,271 @@
+use super::{parse_maze, Maze, Tile};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub const MIN_GENERATED_SIZE: usize = 15;
+pub const MAX_GENERATED_SIZE: usize = 59;
+
+/// Chance of opening a wall between two corridors that are already
+/// connected, so the maze has loops to escape ghosts round.
+const LOOP_CHANCE: f64 = 0.1;
+
+/// Builds a Pac-Man style maze from `seed`: mirrored left to right, with a
+/// ghost house in the middle, Pac-Man just below it, tunnels on both sides,
+/// a power pellet in each corner and no dead ends.
+///
+/// Corridors run along odd rows and columns, so sizes are made odd (rounding
+/// down) and kept between 15 and 59 tiles. The same seed and size always
+/// give the same maze.
+pub fn generate_maze(width: usize, height: usize, seed: u64) -> Maze {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut grid = Grid::new(odd_size(width), odd_size(height));
+    grid.carve_ghost_house();
+    grid.carve_corridors(&mut rng);
+    grid.carve_tunnels(&mut rng);
+    grid.remove_dead_ends(&mut rng);
+    parse_maze(&grid.text()).expect("generated mazes are always valid")
+}
+
+fn odd_size(size: usize) -> usize {
+    let size = size.clamp(MIN_GENERATED_SIZE, MAX_GENERATED_SIZE);
+    size - (1 - size % 2)
+}
+
+/// A square where two corridors might meet: odd row and column.
+type Node = (usize, usize);
+
+struct Grid {
+    symbols: Vec<Vec<char>>,
+    width: usize,
+    height: usize,
+    /// Column of the door; the maze mirrors around it.
+    centre: usize,
+    /// Top wall row of the ghost house.
+    house_top: usize,
+    /// Half-width of the ghost house walls, chosen so the corridor around
+    /// the house lands on odd columns.
+    house_half: usize,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize) -> Self {
+        let centre = width / 2;
+        // Even, so the corridors above and below the house are odd rows.
+        let house_top = ((height - 3) / 2) & !1;
+        Self {
+            symbols: vec![vec![Tile::Wall.symbol(); width]; height],
+            width,
+            height,
+            centre,
+            house_top,
+            house_half: if centre % 2 == 1 { 3 } else { 4 },
+        }
+    }
+
+    fn text(&self) -> String {
+        self.symbols
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    fn mirror_x(&self, x: usize) -> usize {
+        self.width - 1 - x
+    }
+
+    /// Sets a square and its mirror image.
+    fn set(&mut self, x: usize, y: usize, symbol: char) {
+        let mirrored = self.mirror_x(x);
+        self.symbols[y][x] = symbol;
+        self.symbols[y][mirrored] = symbol;
+    }
+
+    fn is_open(&self, x: usize, y: usize) -> bool {
+        self.symbols[y][x] != Tile::Wall.symbol()
+    }
+
+    fn pacman_row(&self) -> usize {
+        self.house_top + 5
+    }
+
+    /// Stamps the house with its door on top and three ghost spawns, rings
+    /// it with an empty corridor and clears Pac-Man's spawn below it.
+    fn carve_ghost_house(&mut self) {
+        let (centre, top, half) = (self.centre, self.house_top, self.house_half);
+        for x in centre - half - 1..=centre {
+            self.set(x, top - 1, Tile::Empty.symbol());
+            self.set(x, top + 3, Tile::Empty.symbol());
+        }
+        for y in top - 1..=top + 3 {
+            self.set(centre - half - 1, y, Tile::Empty.symbol());
+        }
+        for x in centre - half + 1..=centre {
+            self.set(x, top + 1, Tile::Empty.symbol());
+        }
+        self.set(centre - 1, top + 1, 'G');
+        self.set(centre, top + 1, 'G');
+        self.set(centre, top, Tile::GhostDoor.symbol());
+
+        let row = self.pacman_row();
+        self.set(centre - 1, row, Tile::Dot.symbol());
+        self.set(centre, row, 'P');
+    }
+
+    fn nodes(&self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        for y in (1..self.height - 1).step_by(2) {
+            for x in (1..self.width - 1).step_by(2) {
+                if !self.in_house_interior(x, y) {
+                    nodes.push((x, y));
+                }
+            }
+        }
+        nodes
+    }
+
+    /// Whether a square is part of the ghost house itself, walls included.
+    fn in_house_interior(&self, x: usize, y: usize) -> bool {
+        x.abs_diff(self.centre) <= self.house_half
+            && (self.house_top..=self.house_top + 2).contains(&y)
+    }
+
+    /// Every pair of neighbouring nodes, with the square between them. Walls
+    /// of the ghost house are never knocked through.
+    fn edges(&self) -> Vec<(Node, Node, (usize, usize))> {
+        let mut edges = Vec::new();
+        for (x, y) in self.nodes() {
+            for (nx, ny) in [(x + 2, y), (x, y + 2)] {
+                let between = ((x + nx) / 2, (y + ny) / 2);
+                if nx < self.width - 1
+                    && ny < self.height - 1
+                    && !self.in_house_interior(between.0, between.1)
+                    && !self.in_house_interior(nx, ny)
+                {
+                    edges.push(((x, y), (nx, ny), between));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Opens corridors between nodes until every node is connected, taking
+    /// walls in random order and always knocking out the mirror image too.
+    fn carve_corridors(&mut self, rng: &mut impl Rng) {
+        let nodes = self.nodes();
+        for &(x, y) in &nodes {
+            if !self.is_open(x, y) {
+                self.set(x, y, Tile::Dot.symbol());
+            }
+        }
+
+        let index = |node: &Node| nodes.binary_search_by_key(&(node.1, node.0), |n| (n.1, n.0));
+        let mut sets = DisjointSet::new(nodes.len());
+        let mut edges = self.edges();
+        // The corridor round the house is already open, so join it up first.
+        for &(a, b, (x, y)) in &edges {
+            if self.is_open(x, y) {
+                sets.union(index(&a).unwrap(), index(&b).unwrap());
+            }
+        }
+
+        edges.shuffle(rng);
+        for (a, b, (x, y)) in edges {
+            if self.is_open(x, y) {
+                continue;
+            }
+            let mirror_a = (self.mirror_x(a.0), a.1);
+            let mirror_b = (self.mirror_x(b.0), b.1);
+            let joins = sets.union(index(&a).unwrap(), index(&b).unwrap());
+            if joins || rng.gen_bool(LOOP_CHANCE) {
+                sets.union(index(&mirror_a).unwrap(), index(&mirror_b).unwrap());
+                self.set(x, y, Tile::Dot.symbol());
+            }
+        }
+
+        for (x, y) in [(1, 1), (1, self.height - 2)] {
+            self.set(x, y, Tile::PowerPellet.symbol());
+        }
+    }
+
+    /// Opens one tunnel, or two on taller mazes, on rows clear of the house
+    /// and Pac-Man's spawn.
+    fn carve_tunnels(&mut self, rng: &mut impl Rng) {
+        let mut rows: Vec<usize> = (3..self.height - 3)
+            .step_by(2)
+            .filter(|&y| {
+                !(self.house_top - 1..=self.house_top + 3).contains(&y) && y != self.pacman_row()
+            })
+            .collect();
+        rows.shuffle(rng);
+        let wanted = if self.height >= 25 { 2 } else { 1 };
+        let mut chosen: Vec<usize> = Vec::new();
+        for y in rows {
+            if chosen.len() < wanted && chosen.iter().all(|&other| other.abs_diff(y) > 2) {
+                chosen.push(y);
+            }
+        }
+        for y in chosen {
+            self.set(0, y, Tile::Tunnel.symbol());
+        }
+    }
+
+    /// Opens an extra corridor from every node that only has one way out.
+    fn remove_dead_ends(&mut self, rng: &mut impl Rng) {
+        for (x, y) in self.nodes() {
+            let exits = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)];
+            if exits
+                .iter()
+                .filter(|&&(ex, ey)| self.is_open(ex, ey))
+                .count()
+                >= 2
+            {
+                continue;
+            }
+            let mut closed: Vec<(usize, usize)> = exits
+                .into_iter()
+                .filter(|&(ex, ey)| {
+                    ex > 0
+                        && ey > 0
+                        && ex < self.width - 1
+                        && ey < self.height - 1
+                        && !self.is_open(ex, ey)
+                        && !self.in_house_interior(ex, ey)
+                })
+                .collect();
+            closed.shuffle(rng);
+            if let Some(&(ex, ey)) = closed.first() {
+                self.set(ex, ey, Tile::Dot.symbol());
+            }
+        }
+    }
+}
+
+/// Union-find over node indices, for telling which corridors already join.
+struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, mut item: usize) -> usize {
+        while self.parents[item] != item {
+            self.parents[item] = self.parents[self.parents[item]];
+            item = self.parents[item];
+        }
+        item
+    }
+
+    /// Joins the sets holding `a` and `b`, returning `false` if they were
+    /// already one.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a] = b;
+        a != b
+    }
+}
@@ -0,0 +1,194 @@
+//! Mazes are drawn as ASCII art, one character per tile:
+//!
+//! ```text
+//! #  wall            -  ghost house door
+//! .  dot             T  tunnel (wraps when on the edge of a row)
+//! o  power pellet    F  bonus fruit spawn (defaults to P)
+//!    empty           P  Pac-Man's spawn
+//!                    G  ghost spawn inside the house
+//! ```
+//!
+//! `parse_maze` turns the text into a `Maze` or a list of problems, each with
+//! the line and column it was found at.
+
+mod editor;
+mod generator;
+mod pack;
+mod parser;
+mod tile;
+mod validator;
+
+pub use editor::{Brush, MazeDraft};
+pub use generator::generate_maze;
+pub use pack::{maze_index, MazePack, MazeTheme, MAZE_PACKS};
+pub use parser::parse_maze;
+pub use tile::{Tile, Walker};
+pub use validator::validate_maze;
+
+use crate::models::{Direction, Position};
+use std::fmt;
+
+pub const CLASSIC_MAZE: &str = include_str!("classic.txt");
+
+/// A maze and where everyone spawns in it. Blinky starts on `house_exit`,
+/// just outside the ghost house door. The engine keeps a copy of the maze as
+/// loaded and eats its way through another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Maze {
+    pub tiles: Vec<Vec<Tile>>,
+    pub pacman_spawn: Position,
+    pub ghost_spawns: Vec<Position>,
+    pub house_exit: Position,
+    pub fruit_spawn: Position,
+}
+
+impl Maze {
+    pub fn classic() -> Self {
+        parse_maze(CLASSIC_MAZE).expect("the classic maze is valid")
+    }
+
+    pub fn width(&self) -> usize {
+        self.tiles[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn tile(&self, pos: &Position) -> Tile {
+        self.tiles[pos.y][pos.x]
+    }
+
+    pub fn is_tunnel(&self, pos: &Position) -> bool {
+        self.tile(pos) == Tile::Tunnel
+    }
+
+    pub fn is_walkable_for(&self, pos: &Position, walker: Walker) -> bool {
+        self.tile(pos).is_walkable_for(walker)
+    }
+
+    /// Tile one step from `pos` in `direction`. Stepping off the side of the
+    /// maze from a tunnel mouth wraps round to the other end of the row; any
+    /// other step off the edge goes nowhere.
+    pub fn neighbor(&self, pos: &Position, direction: Direction) -> Option<Position> {
+        let last_x = self.width() - 1;
+        let wrap_x = |x: usize| self.is_tunnel(pos).then_some(Position { x, y: pos.y });
+        match direction {
+            Direction::Up => pos.y.checked_sub(1).map(|y| Position { x: pos.x, y }),
+            Direction::Down => (pos.y + 1 < self.height()).then(|| Position {
+                x: pos.x,
+                y: pos.y + 1,
+            }),
+            Direction::Left => match pos.x.checked_sub(1) {
+                Some(x) => Some(Position { x, y: pos.y }),
+                None => wrap_x(last_x),
+            },
+            Direction::Right if pos.x < last_x => Some(Position {
+                x: pos.x + 1,
+                y: pos.y,
+            }),
+            Direction::Right => wrap_x(0),
+            Direction::None => None,
+        }
+    }
+
+    /// The neighbour in `direction` if `walker` may step onto it.
+    pub fn step(&self, pos: &Position, direction: Direction, walker: Walker) -> Option<Position> {
+        self.neighbor(pos, direction)
+            .filter(|next| self.is_walkable_for(next, walker))
+    }
+
+    /// Every tile `walker` can reach in one step from `pos`, in up, down,
+    /// left, right order.
+    pub fn neighbors(&self, pos: &Position, walker: Walker) -> Vec<Position> {
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .filter_map(|direction| self.step(pos, direction, walker))
+        .collect()
+    }
+
+    /// Rows with a tunnel mouth at both ends.
+    pub fn tunnel_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        let last_x = self.width() - 1;
+        (0..self.height()).filter(move |&y| {
+            self.tiles[y][0] == Tile::Tunnel && self.tiles[y][last_x] == Tile::Tunnel
+        })
+    }
+
+    /// Clears a dot or power pellet from `pos`, returning what was eaten.
+    pub fn eat(&mut self, pos: &Position) -> Option<Tile> {
+        let tile = self.tile(pos);
+        tile.is_food().then(|| {
+            self.tiles[pos.y][pos.x] = Tile::Empty;
+            tile
+        })
+    }
+
+    /// Dots and power pellets still to be eaten.
+    pub fn remaining_dots(&self) -> usize {
+        self.tiles
+            .iter()
+            .flatten()
+            .filter(|tile| tile.is_food())
+            .count()
+    }
+}
+
+/// Writes the maze back out in the ASCII format `parse_maze` reads.
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let pos = Position { x, y };
+                let symbol = if pos == self.pacman_spawn {
+                    'P'
+                } else if self.ghost_spawns.contains(&pos) {
+                    'G'
+                } else {
+                    tile.symbol()
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something wrong with a maze. `location` is the 1-based `(line, column)`
+/// of the offending tile, or `None` for problems with the maze as a whole.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeError {
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl MazeError {
+    fn at(pos: &Position, message: impl Into<String>) -> Self {
+        Self {
+            location: Some((pos.y + 1, pos.x + 1)),
+            message: message.into(),
+        }
+    }
+
+    fn whole(message: impl Into<String>) -> Self {
+        Self {
+            location: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use super::{parse_maze, Maze};
+
+/// Wall colours for one maze. Each pair is the two ends of the wall gradient;
+/// `house` paints the band of walls around the ghost house.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MazeTheme {
+    pub wall: (&'static str, &'static str),
+    pub house: (&'static str, &'static str),
+}
+
+impl MazeTheme {
+    pub const CLASSIC: MazeTheme = MazeTheme {
+        wall: ("#00f", "#7889e8"),
+        house: ("#00c0ff", "#00e2f9"),
+    };
+
+    /// Inline style for a wall tile, using the house colours when it sits in
+    /// the band around the ghost house.
+    pub fn wall_style(&self, in_house_band: bool) -> String {
+        let (from, to) = if in_house_band { self.house } else { self.wall };
+        format!("background: linear-gradient(45deg, {}, {})", from, to)
+    }
+}
+
+impl Default for MazeTheme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+/// A bundled maze and the colours it is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PackMaze {
+    pub name: &'static str,
+    pub text: &'static str,
+    pub theme: MazeTheme,
+}
+
+/// An ordered set of mazes played one per level, starting again from the first
+/// once the last is cleared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MazePack {
+    pub name: &'static str,
+    pub mazes: &'static [PackMaze],
+}
+
+impl MazePack {
+    /// Parses every maze in the pack, in play order.
+    pub fn layouts(&self) -> Vec<Maze> {
+        self.mazes
+            .iter()
+            .map(|maze| parse_maze(maze.text).expect("bundled mazes are valid"))
+            .collect()
+    }
+
+    /// The maze played on `level`.
+    pub fn maze_for_level(&self, level: u32) -> &PackMaze {
+        &self.mazes[maze_index(level, self.mazes.len())]
+    }
+
+    /// Index of the pack whose mazes are exactly `layouts`, if any.
+    pub fn find(layouts: &[Maze]) -> Option<usize> {
+        MAZE_PACKS.iter().position(|pack| pack.layouts() == layouts)
+    }
+}
+
+/// Which of `count` mazes is played on `level`, cycling from level 1.
+pub fn maze_index(level: u32, count: usize) -> usize {
+    (level.max(1) as usize - 1) % count
+}
+
+const CLASSIC: PackMaze = PackMaze {
+    name: "Classic",
+    text: super::CLASSIC_MAZE,
+    theme: MazeTheme::CLASSIC,
+};
+
+/// Every pack offered on the start menu. The first is the default game.
+pub const MAZE_PACKS: [MazePack; 2] = [
+    MazePack {
+        name: "Classic",
+        mazes: &[CLASSIC],
+    },
+    MazePack {
+        name: "Grand Tour",
+        mazes: &[
+            CLASSIC,
+            PackMaze {
+                name: "Crossroads",
+                text: include_str!("crossroads.txt"),
+                theme: MazeTheme {
+                    wall: ("#c2185b", "#ff80ab"),
+                    house: ("#ff6f00", "#ffd180"),
+                },
+            },
+            PackMaze {
+                name: "Long Halls",
+                text: include_str!("long_halls.txt"),
+                theme: MazeTheme {
+                    wall: ("#2e7d32", "#9ccc65"),
+                    house: ("#00897b", "#64ffda"),
+                },
+            },
+            PackMaze {
+                name: "Twin Tunnels",
+                text: include_str!("twin_tunnels.txt"),
+                theme: MazeTheme {
+                    wall: ("#6a1b9a", "#ce93d8"),
+                    house: ("#d84315", "#ffab91"),
+                },
+            },
+        ],
+    },
+];
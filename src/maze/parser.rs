@@ -0,0 +1,121 @@
+use super::{validate_maze, Maze, MazeError, Tile, Walker};
+use crate::models::Position;
+
+const GHOST_SPAWNS: usize = 3;
+
+/// Reads an ASCII maze and checks it is playable, returning every problem
+/// found rather than stopping at the first.
+pub fn parse_maze(text: &str) -> Result<Maze, Vec<MazeError>> {
+    let lines: Vec<&str> = text.trim_end_matches(['\r', '\n']).lines().collect();
+    if lines.iter().all(|line| line.is_empty()) {
+        return Err(vec![MazeError::whole("maze is empty")]);
+    }
+
+    let width = lines[0].chars().count();
+    let mut errors = Vec::new();
+    let mut tiles = Vec::new();
+    let mut pacman_spawn = None;
+    let mut fruit_spawn = None;
+    let mut ghost_spawns = Vec::new();
+    let mut doors = Vec::new();
+
+    for (y, line) in lines.iter().enumerate() {
+        let mut row = Vec::new();
+        for (x, symbol) in line.chars().enumerate() {
+            let pos = Position { x, y };
+            let tile = match symbol {
+                'P' => {
+                    if pacman_spawn.is_some() {
+                        errors.push(MazeError::at(&pos, "second Pac-Man spawn (P)"));
+                    }
+                    pacman_spawn.get_or_insert(pos);
+                    Tile::Empty
+                }
+                'G' => {
+                    ghost_spawns.push(pos);
+                    Tile::Empty
+                }
+                _ => match Tile::from_symbol(symbol) {
+                    Some(Tile::GhostDoor) => {
+                        doors.push(pos);
+                        Tile::GhostDoor
+                    }
+                    Some(Tile::FruitSpawn) => {
+                        if fruit_spawn.is_some() {
+                            errors.push(MazeError::at(&pos, "second fruit spawn (F)"));
+                        }
+                        fruit_spawn.get_or_insert(pos);
+                        Tile::FruitSpawn
+                    }
+                    Some(tile) => tile,
+                    None => {
+                        errors.push(MazeError::at(&pos, format!("unknown tile '{}'", symbol)));
+                        Tile::Empty
+                    }
+                },
+            };
+            row.push(tile);
+        }
+
+        if row.len() != width {
+            errors.push(MazeError::at(
+                &Position {
+                    x: row.len().min(width),
+                    y,
+                },
+                format!("row is {} tiles wide, expected {}", row.len(), width),
+            ));
+            row.resize(width, Tile::Wall);
+        }
+        tiles.push(row);
+    }
+
+    if pacman_spawn.is_none() {
+        errors.push(MazeError::whole("no Pac-Man spawn (P)"));
+    }
+    if ghost_spawns.len() != GHOST_SPAWNS {
+        errors.push(MazeError::whole(format!(
+            "expected {} ghost spawns (G), found {}",
+            GHOST_SPAWNS,
+            ghost_spawns.len()
+        )));
+    }
+    if doors.is_empty() {
+        errors.push(MazeError::whole("no ghost house door (-)"));
+    }
+    let (Some(pacman_spawn), [door, ..], true) =
+        (pacman_spawn, doors.as_slice(), errors.is_empty())
+    else {
+        return Err(errors);
+    };
+
+    let mut maze = Maze {
+        tiles,
+        fruit_spawn: fruit_spawn.unwrap_or_else(|| pacman_spawn.clone()),
+        pacman_spawn,
+        ghost_spawns,
+        house_exit: door.clone(),
+    };
+    let Some(house_exit) = find_house_exit(&maze, door) else {
+        return Err(vec![MazeError::at(
+            door,
+            "ghost house door does not open onto the maze",
+        )]);
+    };
+    maze.house_exit = house_exit;
+
+    let errors = validate_maze(&maze);
+    if errors.is_empty() {
+        Ok(maze)
+    } else {
+        Err(errors)
+    }
+}
+
+/// The tile just outside `door`: the neighbour Pac-Man can reach.
+fn find_house_exit(maze: &Maze, door: &Position) -> Option<Position> {
+    let reachable = super::validator::reachable_tiles(maze);
+    maze.neighbors(door, Walker::HouseGhost)
+        .into_iter()
+        .find(|pos| reachable[pos.y][pos.x])
+}
@@ -0,0 +1,66 @@
+/// What occupies one square of the maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Dot,
+    PowerPellet,
+    /// The ghost house door: ghosts going in or out may pass it, Pac-Man may not.
+    GhostDoor,
+    /// A tunnel mouth: stepping off the edge of the maze from here comes back
+    /// in on the opposite side of the row.
+    Tunnel,
+    /// Where bonus fruit appears. Walkable, but never holds a dot.
+    FruitSpawn,
+}
+
+/// Who is trying to move, since the ghost door only lets some through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Walker {
+    Pacman,
+    Ghost,
+    /// A ghost entering or leaving the house, allowed through the door.
+    HouseGhost,
+}
+
+impl Tile {
+    /// The tile drawn as `symbol` in an ASCII maze. Spawn markers are not
+    /// tiles; the parser reads them as `Empty`.
+    pub fn from_symbol(symbol: char) -> Option<Tile> {
+        match symbol {
+            ' ' => Some(Tile::Empty),
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Dot),
+            'o' => Some(Tile::PowerPellet),
+            '-' => Some(Tile::GhostDoor),
+            'T' => Some(Tile::Tunnel),
+            'F' => Some(Tile::FruitSpawn),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Dot => '.',
+            Tile::PowerPellet => 'o',
+            Tile::GhostDoor => '-',
+            Tile::Tunnel => 'T',
+            Tile::FruitSpawn => 'F',
+        }
+    }
+
+    /// Dots and power pellets: what Pac-Man has to clear to finish a level.
+    pub fn is_food(self) -> bool {
+        matches!(self, Tile::Dot | Tile::PowerPellet)
+    }
+
+    pub fn is_walkable_for(self, walker: Walker) -> bool {
+        match self {
+            Tile::Wall => false,
+            Tile::GhostDoor => walker == Walker::HouseGhost,
+            _ => true,
+        }
+    }
+}
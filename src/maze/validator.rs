@@ -0,0 +1,49 @@
+use super::{Maze, MazeError, Tile, Walker};
+use crate::models::Position;
+use std::collections::VecDeque;
+
+/// Checks a maze can be played to the end: every dot must be reachable from
+/// Pac-Man's spawn and every tunnel on the edge must come out somewhere.
+pub fn validate_maze(maze: &Maze) -> Vec<MazeError> {
+    let mut errors = Vec::new();
+    let reachable = reachable_tiles(maze);
+    let last_x = maze.width() - 1;
+
+    for (y, row) in maze.tiles.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            let pos = Position { x, y };
+            if tile.is_food() && !reachable[y][x] {
+                errors.push(MazeError::at(
+                    &pos,
+                    "dot cannot be reached from the Pac-Man spawn",
+                ));
+            }
+            let on_edge = x == 0 || x == last_x;
+            if tile == Tile::Tunnel && on_edge && row[last_x - x] != Tile::Tunnel {
+                errors.push(MazeError::at(
+                    &pos,
+                    "tunnel has no exit on the other side of the row",
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Tiles Pac-Man can walk to from his spawn, found breadth-first.
+pub(super) fn reachable_tiles(maze: &Maze) -> Vec<Vec<bool>> {
+    let start = &maze.pacman_spawn;
+    let mut reachable = vec![vec![false; maze.width()]; maze.height()];
+    reachable[start.y][start.x] = true;
+    let mut queue = VecDeque::from([start.clone()]);
+
+    while let Some(pos) = queue.pop_front() {
+        for next in maze.neighbors(&pos, Walker::Pacman) {
+            if !reachable[next.y][next.x] {
+                reachable[next.y][next.x] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    reachable
+}
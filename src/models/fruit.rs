@@ -0,0 +1,53 @@
+/// Bonus fruit. Each level has its own, worth more the further you get.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fruit {
+    Cherry,
+    Strawberry,
+    Orange,
+    Apple,
+    Melon,
+    Galaxian,
+    Bell,
+    Key,
+}
+
+impl Fruit {
+    pub fn for_level(level: u32) -> Self {
+        match level {
+            0 | 1 => Fruit::Cherry,
+            2 => Fruit::Strawberry,
+            3 | 4 => Fruit::Orange,
+            5 | 6 => Fruit::Apple,
+            7 | 8 => Fruit::Melon,
+            9 | 10 => Fruit::Galaxian,
+            11 | 12 => Fruit::Bell,
+            _ => Fruit::Key,
+        }
+    }
+
+    pub fn points(self) -> i32 {
+        match self {
+            Fruit::Cherry => 100,
+            Fruit::Strawberry => 300,
+            Fruit::Orange => 500,
+            Fruit::Apple => 700,
+            Fruit::Melon => 1000,
+            Fruit::Galaxian => 2000,
+            Fruit::Bell => 3000,
+            Fruit::Key => 5000,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Fruit::Cherry => "🍒",
+            Fruit::Strawberry => "🍓",
+            Fruit::Orange => "🍊",
+            Fruit::Apple => "🍎",
+            Fruit::Melon => "🍈",
+            Fruit::Galaxian => "🚀",
+            Fruit::Bell => "🔔",
+            Fruit::Key => "🔑",
+        }
+    }
+}
@@ -1,31 +1,114 @@
-use super::Position;
+use super::{Direction, Motion, Position};
+use crate::maze::Maze;
 
-#[derive(Clone, PartialEq)]
+/// What a ghost is currently doing. Scatter and Chase alternate on a timer,
+/// Frightened follows a power pellet, Eaten ghosts head home as eyes,
+/// InHouse ghosts wait in the ghost house until they are released and
+/// Leaving ghosts are on their way out through the door.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GhostMode {
+    Scatter,
+    Chase,
+    Frightened,
+    Eaten,
+    InHouse,
+    Leaving,
+}
+
+/// The four arcade ghosts. Each has its own colour, scatter corner and way of
+/// picking a chase target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GhostId {
+    Blinky,
+    Pinky,
+    Inky,
+    Clyde,
+}
+
+impl GhostId {
+    pub fn color(self) -> &'static str {
+        match self {
+            GhostId::Blinky => "#FF0000",
+            GhostId::Pinky => "#FFB8FF",
+            GhostId::Inky => "#00FFFF",
+            GhostId::Clyde => "#FFB852",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Ghost {
+    pub id: GhostId,
     pub position: Position,
-    pub color: &'static str,
+    pub mode: GhostMode,
+    pub direction: Direction,
+    pub home: Position,
+    pub scatter_target: Position,
+    pub reverse_pending: bool,
+    pub motion: Motion,
+    /// Cruise Elroy stage, 0 to 2. Only Blinky ever gets one.
+    pub elroy: u8,
 }
 
 impl Ghost {
-    pub fn initialize_ghosts(_maze: &[Vec<u8>]) -> Vec<Ghost> {
-        // Hard-coded center positions for ghosts
+    pub fn new(id: GhostId, position: Position, scatter_target: Position) -> Self {
+        Ghost {
+            id,
+            home: position.clone(),
+            position,
+            mode: GhostMode::Scatter,
+            direction: Direction::None,
+            scatter_target,
+            reverse_pending: false,
+            motion: Motion::default(),
+            elroy: 0,
+        }
+    }
+
+    pub fn initialize_ghosts(maze: &Maze) -> Vec<Ghost> {
+        let right = maze.width() - 1;
+        let bottom = maze.height() - 1;
+        // Mazes without enough spawns stack the spare ghosts on the last one
+        let spawn = |i: usize| {
+            maze.ghost_spawns
+                .get(i)
+                .or(maze.ghost_spawns.last())
+                .unwrap_or(&maze.house_exit)
+                .clone()
+        };
+
+        // Blinky starts outside the door, the others wait inside the ghost house
+        let mut blinky = Ghost::new(GhostId::Blinky, maze.house_exit.clone(), Position { x: right, y: 0 });  // Red ghost
+        blinky.home = spawn(1);
         vec![
-            Ghost {
-                position: Position { x: 14, y: 11 },  // Red ghost in center
-                color: "#FF0000"
-            },
-            Ghost {
-                position: Position { x: 14, y: 12 },  // Cyan ghost
-                color: "#00FFFF"
-            },
-            Ghost {
-                position: Position { x: 15, y: 11 },  // Pink ghost
-                color: "#FFB8FF"
-            },
-            Ghost {
-            position: Position { x: 15, y: 12 },  // Orange ghost
-                color: "#FFB852"
-            }
+            blinky,
+            Ghost::in_house(GhostId::Inky, spawn(0), Position { x: right, y: bottom }),  // Cyan ghost
+            Ghost::in_house(GhostId::Pinky, spawn(1), Position { x: 0, y: 0 }),  // Pink ghost
+            Ghost::in_house(GhostId::Clyde, spawn(2), Position { x: 0, y: bottom }),  // Orange ghost
         ]
     }
+
+    pub fn in_house(id: GhostId, home: Position, scatter_target: Position) -> Self {
+        Ghost {
+            mode: GhostMode::InHouse,
+            ..Ghost::new(id, home, scatter_target)
+        }
+    }
+
+    /// Whether touching this ghost costs Pac-Man a life.
+    pub fn is_dangerous(&self) -> bool {
+        matches!(self.mode, GhostMode::Scatter | GhostMode::Chase)
+    }
+
+    /// Switches mode, turning the ghost around as the arcade does when leaving
+    /// Scatter or Chase.
+    pub fn set_mode(&mut self, mode: GhostMode) {
+        if self.mode == mode {
+            return;
+        }
+        if self.is_dangerous() {
+            self.reverse_pending = true;
+        }
+        self.mode = mode;
+    }
 }
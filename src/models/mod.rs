@@ -1,7 +1,11 @@
 mod direction;
+mod fruit;
 mod ghost;
+mod motion;
 mod position;
 
 pub use direction::Direction;
-pub use ghost::Ghost;
+pub use fruit::Fruit;
+pub use ghost::{Ghost, GhostId, GhostMode};
+pub use motion::Motion;
 pub use position::Position;
@@ -0,0 +1,33 @@
+use crate::constants::game::SUBTILES_PER_TILE;
+
+/// Fixed-point progress of an actor from its tile towards the next one, in
+/// `SUBTILES_PER_TILE`ths of a tile. Logic still happens tile by tile; the
+/// sub-tile part lets actors move at any speed and glide when drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Motion {
+    pub progress: u32,
+    /// Set on the tick the actor came out of the far end of a tunnel, so the
+    /// board can draw the jump without sliding across the maze.
+    pub wrapped: bool,
+}
+
+impl Motion {
+    /// Travels `speed` sub-tiles and returns how many whole tiles were
+    /// completed, which the caller turns into tile steps.
+    pub fn advance(&mut self, speed: u32) -> u32 {
+        self.wrapped = false;
+        self.progress += speed;
+        let tiles = self.progress / SUBTILES_PER_TILE;
+        self.progress %= SUBTILES_PER_TILE;
+        tiles
+    }
+
+    /// Drops any partial progress, for an actor that is blocked.
+    pub fn stop(&mut self) {
+        self.progress = 0;
+    }
+
+    pub fn fraction(&self) -> f64 {
+        f64::from(self.progress) / f64::from(SUBTILES_PER_TILE)
+    }
+}
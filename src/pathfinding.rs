@@ -0,0 +1,176 @@
+//! Searches over the maze graph. Every walkable tile is a node and each step
+//! to a neighbour, including the wrap through a tunnel, costs one.
+
+use crate::maze::{Maze, Walker};
+use crate::models::Position;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// How ghosts steer towards their target tile outside the ghost house.
+/// `Greedy` is the arcade rule: take whichever neighbouring tile is closest to
+/// the target in a straight line, even if a wall is in the way. `ShortestPath`
+/// follows a true shortest route through the maze instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum GhostAi {
+    #[default]
+    Greedy,
+    ShortestPath,
+}
+
+/// Breadth-first flood from `from`: the number of steps to every tile the
+/// walker can reach, `None` for the rest.
+pub fn bfs(maze: &Maze, from: &Position, walker: Walker) -> Vec<Vec<Option<u32>>> {
+    let mut steps = vec![vec![None; maze.width()]; maze.height()];
+    steps[from.y][from.x] = Some(0);
+    let mut queue = VecDeque::from([(from.clone(), 0)]);
+    while let Some((pos, distance)) = queue.pop_front() {
+        for next in maze.neighbors(&pos, walker) {
+            if steps[next.y][next.x].is_none() {
+                steps[next.y][next.x] = Some(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    steps
+}
+
+/// A* search for a shortest path from `from` to `to`, not counting `from`.
+/// Returns an empty path when already there and `None` when `to` can't be
+/// reached. The heuristic is the Manhattan distance, measured the short way
+/// round through a tunnel when the maze has one, so it never overestimates.
+pub fn a_star(
+    maze: &Maze,
+    from: &Position,
+    to: &Position,
+    walker: Walker,
+) -> Option<Vec<Position>> {
+    let wraps = maze.tunnel_rows().next().is_some();
+    let heuristic = |pos: &Position| {
+        let dx = pos.x.abs_diff(to.x);
+        let dx = if wraps { dx.min(maze.width() - dx) } else { dx };
+        (dx + pos.y.abs_diff(to.y)) as u32
+    };
+
+    let mut came_from = vec![vec![None::<Position>; maze.width()]; maze.height()];
+    let mut cost = vec![vec![u32::MAX; maze.width()]; maze.height()];
+    cost[from.y][from.x] = 0;
+    // Ties on estimated total cost go to the tile queued first, which keeps
+    // the chosen path stable.
+    let mut queued = 0_u32;
+    let mut open = BinaryHeap::from([Reverse((heuristic(from), queued, from.x, from.y))]);
+
+    while let Some(Reverse((_, _, x, y))) = open.pop() {
+        let pos = Position { x, y };
+        if pos == *to {
+            return Some(trace_path(&came_from, from, to));
+        }
+        let next_cost = cost[y][x] + 1;
+        for next in maze.neighbors(&pos, walker) {
+            if next_cost < cost[next.y][next.x] {
+                cost[next.y][next.x] = next_cost;
+                came_from[next.y][next.x] = Some(pos.clone());
+                queued += 1;
+                open.push(Reverse((
+                    next_cost + heuristic(&next),
+                    queued,
+                    next.x,
+                    next.y,
+                )));
+            }
+        }
+    }
+    None
+}
+
+fn trace_path(
+    came_from: &[Vec<Option<Position>>],
+    from: &Position,
+    to: &Position,
+) -> Vec<Position> {
+    let mut path = Vec::new();
+    let mut pos = to.clone();
+    while pos != *from {
+        let previous = came_from[pos.y][pos.x].clone().expect("path is connected");
+        path.push(pos);
+        pos = previous;
+    }
+    path.reverse();
+    path
+}
+
+/// Shortest distances between every pair of tiles a walker can stand on,
+/// computed once per maze with a breadth-first search from each tile so
+/// lookups during play are O(1).
+#[derive(Debug, PartialEq)]
+pub struct DistanceTable {
+    width: usize,
+    /// Row-major index of each tile into the table, `None` for tiles the
+    /// walker can't enter.
+    nodes: Vec<Option<usize>>,
+    node_count: usize,
+    distances: Vec<u16>,
+}
+
+const UNREACHABLE: u16 = u16::MAX;
+
+impl DistanceTable {
+    pub fn new(maze: &Maze, walker: Walker) -> Self {
+        let width = maze.width();
+        let tiles: Vec<Position> = (0..maze.height())
+            .flat_map(|y| (0..width).map(move |x| Position { x, y }))
+            .collect();
+        let mut nodes = vec![None; tiles.len()];
+        let mut node_count = 0;
+        for pos in tiles
+            .iter()
+            .filter(|&pos| maze.is_walkable_for(pos, walker))
+        {
+            nodes[pos.y * width + pos.x] = Some(node_count);
+            node_count += 1;
+        }
+
+        let mut distances = vec![UNREACHABLE; node_count * node_count];
+        for start in tiles
+            .iter()
+            .filter(|&pos| maze.is_walkable_for(pos, walker))
+        {
+            let row =
+                nodes[start.y * width + start.x].expect("walkable tiles are nodes") * node_count;
+            for (pos, steps) in tiles
+                .iter()
+                .zip(bfs(maze, start, walker).into_iter().flatten())
+            {
+                if let (Some(node), Some(steps)) = (nodes[pos.y * width + pos.x], steps) {
+                    distances[row + node] = steps.min(u32::from(UNREACHABLE) - 1) as u16;
+                }
+            }
+        }
+
+        Self {
+            width,
+            nodes,
+            node_count,
+            distances,
+        }
+    }
+
+    fn node(&self, pos: &Position) -> Option<usize> {
+        if pos.x >= self.width {
+            return None;
+        }
+        self.nodes
+            .get(pos.y * self.width + pos.x)
+            .copied()
+            .flatten()
+    }
+
+    /// Number of steps from `from` to `to`, or `None` if either tile is off
+    /// limits or there is no way between them.
+    pub fn distance(&self, from: &Position, to: &Position) -> Option<u32> {
+        let from = self.node(from)?;
+        let to = self.node(to)?;
+        let distance = self.distances[from * self.node_count + to];
+        (distance != UNREACHABLE).then_some(u32::from(distance))
+    }
+}
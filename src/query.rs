@@ -0,0 +1,33 @@
+/// Reads `seed=<u64>` out of a URL query string such as `?seed=42&foo=bar`.
+pub fn parse_seed(search: &str) -> Option<u64> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("seed="))
+        .and_then(|value| value.parse().ok())
+}
+
+pub fn seed_from_url() -> Option<u64> {
+    parse_seed(&url_search()?)
+}
+
+/// Reads `ghosts=<name>,<name>,...` out of a URL query string: the brain for
+/// each ghost in Blinky, Pinky, Inky, Clyde order.
+pub fn parse_ghost_brains(search: &str) -> Vec<String> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("ghosts="))
+        .map(|value| value.split(',').map(str::to_lowercase).collect())
+        .unwrap_or_default()
+}
+
+pub fn ghost_brains_from_url() -> Vec<String> {
+    url_search()
+        .map(|search| parse_ghost_brains(&search))
+        .unwrap_or_default()
+}
+
+fn url_search() -> Option<String> {
+    web_sys::window()?.location().search().ok()
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use crate::brain::{GhostBrain, GhostBrains, GhostView, Shadow};
+    use crate::constants::game::SUBTILES_PER_TILE;
+    use crate::engine::{GameRng, GameState};
+    use crate::game_logic::move_ghosts;
+    use crate::maze::Maze;
+    use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+    use crate::query::parse_ghost_brains;
+    use crate::tests::maze_from;
+    use rand::RngCore;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Always asks for the same direction and counts how often it was asked.
+    struct Stubborn {
+        direction: Direction,
+        calls: Cell<u32>,
+    }
+
+    impl Stubborn {
+        fn new(direction: Direction) -> Rc<Self> {
+            Rc::new(Self {
+                direction,
+                calls: Cell::new(0),
+            })
+        }
+    }
+
+    impl GhostBrain for Stubborn {
+        fn choose_direction(
+            &self,
+            _ghost: &Ghost,
+            _view: &GhostView,
+            _rng: &mut dyn RngCore,
+        ) -> Direction {
+            self.calls.set(self.calls.get() + 1);
+            self.direction
+        }
+    }
+
+    /// An open 5x5 room inside a wall.
+    fn create_room() -> Maze {
+        maze_from(&[
+            "#######", "#     #", "#     #", "#     #", "#     #", "#     #", "#######",
+        ])
+    }
+
+    fn chaser(id: GhostId, position: Position) -> Ghost {
+        let mut ghost = Ghost::new(id, position, Position { x: 0, y: 0 });
+        ghost.mode = GhostMode::Chase;
+        ghost
+    }
+
+    fn step(ghosts: &mut [Ghost], pacman_pos: &Position, maze: &Maze, brains: &GhostBrains) {
+        let snapshot = ghosts.to_vec();
+        let view = GhostView {
+            maze,
+            pacman_pos,
+            pacman_direction: Direction::Left,
+            ghosts: &snapshot,
+            distances: None,
+        };
+        let speeds = vec![SUBTILES_PER_TILE; ghosts.len()];
+        move_ghosts(ghosts, &view, brains, &speeds, &mut GameRng::new(1));
+    }
+
+    #[test]
+    fn test_shadow_heads_for_pacman() {
+        let maze = create_room();
+        let ghost = chaser(GhostId::Pinky, Position { x: 3, y: 3 });
+        let pacman_pos = Position { x: 3, y: 5 };
+        let ghosts = vec![ghost.clone()];
+        let view = GhostView {
+            maze: &maze,
+            pacman_pos: &pacman_pos,
+            pacman_direction: Direction::Left,
+            ghosts: &ghosts,
+            distances: None,
+        };
+
+        let direction = Shadow.choose_direction(&ghost, &view, &mut GameRng::new(1));
+        assert_eq!(direction, Direction::Down);
+    }
+
+    #[test]
+    fn test_each_ghost_uses_its_own_personality() {
+        let maze = create_room();
+        let pacman_pos = Position { x: 3, y: 5 };
+        // Pac-Man faces left, so Pinky ambushes at the left wall rather than
+        // heading straight for him like Blinky.
+        let mut ghosts = vec![
+            chaser(GhostId::Blinky, Position { x: 3, y: 3 }),
+            chaser(GhostId::Pinky, Position { x: 3, y: 3 }),
+        ];
+        step(&mut ghosts, &pacman_pos, &maze, &GhostBrains::default());
+        assert_eq!(ghosts[0].direction, Direction::Down);
+        assert_eq!(ghosts[1].direction, Direction::Left);
+
+        let mut ghosts = vec![chaser(GhostId::Pinky, Position { x: 3, y: 3 })];
+        let mut brains = GhostBrains::default();
+        brains.register(GhostId::Pinky, Rc::new(Shadow));
+        step(&mut ghosts, &pacman_pos, &maze, &brains);
+        assert_eq!(ghosts[0].direction, Direction::Down);
+    }
+
+    #[test]
+    fn test_custom_brain_steers_only_its_own_ghost() {
+        let maze = create_room();
+        let pacman_pos = Position { x: 3, y: 5 };
+        let mut brains = GhostBrains::default();
+        brains.register(GhostId::Clyde, Stubborn::new(Direction::Right));
+        let mut ghosts = vec![
+            chaser(GhostId::Blinky, Position { x: 3, y: 3 }),
+            chaser(GhostId::Clyde, Position { x: 3, y: 3 }),
+        ];
+
+        step(&mut ghosts, &pacman_pos, &maze, &brains);
+        assert_eq!(ghosts[0].position, Position { x: 3, y: 4 });
+        assert_eq!(ghosts[1].position, Position { x: 4, y: 3 });
+    }
+
+    #[test]
+    fn test_illegal_choice_falls_back_to_first_open_way() {
+        let maze = create_room();
+        let pacman_pos = Position { x: 3, y: 5 };
+        let mut brains = GhostBrains::default();
+        brains.register(GhostId::Blinky, Stubborn::new(Direction::Left));
+
+        // Heading right, so turning back left is not allowed: Up is first.
+        let mut ghost = chaser(GhostId::Blinky, Position { x: 3, y: 3 });
+        ghost.direction = Direction::Right;
+        let mut ghosts = vec![ghost];
+        step(&mut ghosts, &pacman_pos, &maze, &brains);
+        assert_eq!(ghosts[0].position, Position { x: 3, y: 2 });
+
+        // Against the left wall, Left is blocked.
+        let mut ghosts = vec![chaser(GhostId::Blinky, Position { x: 1, y: 3 })];
+        step(&mut ghosts, &pacman_pos, &maze, &brains);
+        assert_eq!(ghosts[0].position, Position { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn test_brain_is_not_asked_about_house_or_eyes() {
+        let maze = create_room();
+        let brain = Stubborn::new(Direction::Left);
+        let mut brains = GhostBrains::default();
+        brains.register(GhostId::Inky, brain.clone());
+
+        let mut ghost = chaser(GhostId::Inky, Position { x: 3, y: 3 });
+        ghost.mode = GhostMode::Eaten;
+        ghost.home = Position { x: 5, y: 3 };
+        let mut ghosts = vec![ghost];
+        step(&mut ghosts, &Position { x: 1, y: 1 }, &maze, &brains);
+        assert_eq!(ghosts[0].position, Position { x: 4, y: 3 });
+        assert_eq!(brain.calls.get(), 0);
+
+        ghosts[0].mode = GhostMode::Chase;
+        step(&mut ghosts, &Position { x: 1, y: 1 }, &maze, &brains);
+        assert_eq!(brain.calls.get(), 1);
+    }
+
+    #[test]
+    fn test_brains_by_name() {
+        let names = parse_ghost_brains("?seed=3&ghosts=Shadow,shadow,nobody");
+        assert_eq!(names, vec!["shadow", "shadow", "nobody"]);
+        assert!(parse_ghost_brains("?seed=3").is_empty());
+
+        let mut ghosts = vec![chaser(GhostId::Pinky, Position { x: 3, y: 3 })];
+        let brains = GhostBrains::from_names(&names);
+        step(
+            &mut ghosts,
+            &Position { x: 3, y: 5 },
+            &create_room(),
+            &brains,
+        );
+        assert_eq!(ghosts[0].direction, Direction::Down);
+        assert!(GhostBrains::from_names(&[]) == GhostBrains::default());
+    }
+
+    #[test]
+    fn test_registered_brains_survive_restart() {
+        let mut state = GameState::new(1);
+        let brain: Rc<dyn GhostBrain> = Stubborn::new(Direction::Up);
+        state.brains.register(GhostId::Blinky, brain);
+        let brains = state.brains.clone();
+
+        state.restart(2);
+        assert!(state.brains == brains);
+        assert!(state.brains != GhostBrains::default());
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::game::SUBTILES_PER_TILE;
+    use crate::engine::{ghosts_met, GameEvent, GamePhase, GameState, Input};
+    use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+    use crate::tests::maze_from;
+
+    fn pos(x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    fn met(pacman: &[Position], ghost: &[Position]) -> bool {
+        ghosts_met(pacman, &[ghost.to_vec()])[0]
+    }
+
+    #[test]
+    fn test_ending_on_the_same_tile_meets() {
+        assert!(met(&[pos(1, 1), pos(2, 1)], &[pos(3, 1), pos(2, 1)]));
+    }
+
+    #[test]
+    fn test_swapping_tiles_meets() {
+        assert!(met(&[pos(2, 1), pos(3, 1)], &[pos(3, 1), pos(2, 1)]));
+    }
+
+    #[test]
+    fn test_swapping_through_a_tunnel_meets() {
+        assert!(met(&[pos(0, 1), pos(9, 1)], &[pos(9, 1), pos(0, 1)]));
+    }
+
+    #[test]
+    fn test_swapping_mid_trail_meets() {
+        // A fast ghost crosses Pac-Man on its second step of the tick.
+        assert!(met(
+            &[pos(3, 1), pos(4, 1)],
+            &[pos(5, 1), pos(4, 1), pos(3, 1)]
+        ));
+    }
+
+    #[test]
+    fn test_crossing_mid_trail_meets() {
+        // Pac-Man passes through (2, 2) halfway through the tick, just as the
+        // ghost arrives there from below.
+        assert!(met(
+            &[pos(1, 2), pos(2, 2), pos(3, 2)],
+            &[pos(2, 3), pos(2, 2)]
+        ));
+        assert!(met(
+            &[pos(2, 3), pos(2, 2)],
+            &[pos(1, 2), pos(2, 2), pos(3, 2)]
+        ));
+    }
+
+    #[test]
+    fn test_passing_a_tile_before_the_other_arrives_does_not_meet() {
+        // Pac-Man is through (2, 2) early in the tick; the ghost only reaches
+        // it at the very end of its second step.
+        assert!(!met(
+            &[pos(2, 2), pos(3, 2)],
+            &[pos(2, 4), pos(2, 3), pos(2, 2)]
+        ));
+    }
+
+    #[test]
+    fn test_running_over_a_standing_actor_meets() {
+        assert!(met(&[pos(2, 1)], &[pos(2, 1), pos(3, 1)]));
+        assert!(met(&[pos(1, 1), pos(2, 1), pos(3, 1)], &[pos(2, 1)]));
+    }
+
+    #[test]
+    fn test_following_one_tile_behind_does_not_meet() {
+        assert!(!met(&[pos(2, 1), pos(3, 1)], &[pos(1, 1), pos(2, 1)]));
+    }
+
+    #[test]
+    fn test_passing_on_parallel_edges_does_not_meet() {
+        assert!(!met(&[pos(1, 1), pos(2, 1)], &[pos(2, 2), pos(1, 2)]));
+    }
+
+    /// Pac-Man on (2, 1) heading right and a ghost on (3, 1) heading left,
+    /// both one sub-tile short of their next step, so they swap tiles on the
+    /// first tick.
+    fn create_head_on_state(mode: GhostMode) -> GameState {
+        let mut state = GameState::from_maze(maze_from(&["########", "#.P    #", "########"]), 1);
+        let mut ghost = Ghost::new(GhostId::Blinky, pos(3, 1), pos(0, 0));
+        ghost.mode = mode;
+        ghost.direction = Direction::Left;
+        ghost.motion.progress = SUBTILES_PER_TILE - 1;
+        state.ghosts = vec![ghost];
+        state.start();
+        state.direction = Direction::Right;
+        state.pacman_motion.progress = SUBTILES_PER_TILE - 1;
+        state
+    }
+
+    #[test]
+    fn test_head_on_swap_catches_pacman() {
+        let mut state = create_head_on_state(GhostMode::Chase);
+        let events = state.step(Input::default());
+
+        assert_eq!(state.pacman_pos, pos(3, 1));
+        assert_eq!(state.ghosts[0].position, pos(2, 1));
+        assert!(events.contains(&GameEvent::PacmanCaught));
+        assert_eq!(state.phase, GamePhase::Dying);
+    }
+
+    #[test]
+    fn test_head_on_swap_with_frightened_ghost_eats_it() {
+        let mut state = create_head_on_state(GhostMode::Frightened);
+        let events = state.step(Input::default());
+
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::GhostEaten { .. })));
+        assert!(!events.contains(&GameEvent::PacmanCaught));
+        assert_eq!(state.ghosts[0].mode, GhostMode::Eaten);
+        assert_eq!(state.phase, GamePhase::Playing);
+    }
+}
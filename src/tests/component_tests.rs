@@ -1,9 +1,13 @@
 #[cfg(test)]
 mod tests {
     use crate::components::cell::CellProps;
-    use crate::components::game_board::GameBoardProps;
+    use crate::components::game_board::{house_band, wall_style, GameBoardProps};
+    use crate::components::maze_editor::DraftAction;
     use crate::components::scoreboard::{Scoreboard, ScoreboardProps};
-    use crate::models::Position;
+    use crate::maze::{parse_maze, Brush, Maze, MazeDraft, MazeTheme, Tile, MAZE_PACKS};
+    use crate::models::{Direction, Motion, Position};
+    use crate::tests::maze_from;
+    use std::rc::Rc;
     use yew::prelude::*;
 
     #[function_component(TestApp)]
@@ -12,12 +16,25 @@ mod tests {
             <Scoreboard
                 score={props.score}
                 lives={props.lives}
+                life_gained={props.life_gained}
+                seed={props.seed}
                 restart_timer={props.restart_timer}
                 game_over={props.game_over}
-                game_won={props.game_won}
+                level={props.level}
+                level_complete={props.level_complete}
+                collected_fruit={props.collected_fruit.clone()}
                 game_started={props.game_started}
+                paused={props.paused}
+                replay={props.replay.clone()}
+                is_replaying={props.is_replaying}
                 on_restart={props.on_restart.clone()}
                 on_start={props.on_start.clone()}
+                on_edit_maze={props.on_edit_maze.clone()}
+                maze_pack={props.maze_pack}
+                on_select_pack={props.on_select_pack.clone()}
+                ghost_ai={props.ghost_ai}
+                on_toggle_ghost_ai={props.on_toggle_ghost_ai.clone()}
+                on_load_replay={props.on_load_replay.clone()}
             />
         }
     }
@@ -25,19 +42,17 @@ mod tests {
     #[test]
     fn test_cell_props_creation() {
         let props = CellProps {
-            cell_type: 2,
-            is_pacman: false,
-            ghost: None,
-            is_dying: false,
-            is_invincible: false,
-            custom_style:None,
+            tile: Tile::Dot,
+            custom_style: None,
+            popup: None,
+            fruit: None,
+            onclick: Callback::noop(),
+            children: Html::default(),
         };
 
-        assert_eq!(props.cell_type, 2);
-        assert!(!props.is_pacman);
-        assert!(props.ghost.is_none());
-        assert!(!props.is_dying);
-        assert!(!props.is_invincible);
+        assert_eq!(props.tile, Tile::Dot);
+        assert!(props.popup.is_none());
+        assert!(props.fruit.is_none());
     }
 
     #[test]
@@ -45,21 +60,98 @@ mod tests {
         let props = GameBoardProps {
             score: 100,
             game_over: false,
-            maze: vec![vec![0, 1, 2], vec![2, 3, 0]],
+            maze: maze_from(&[" #.", ".o "]),
+            theme: MazeTheme::default(),
             pacman_pos: Position { x: 0, y: 0 },
+            pacman_direction: Direction::None,
+            pacman_motion: Motion::default(),
             ghosts: vec![],
+            popups: vec![],
+            fruit: None,
             is_dying: false,
             is_invincible: false,
         };
 
         assert_eq!(props.score, 100);
         assert!(!props.game_over);
-        assert_eq!(props.maze.len(), 2);
-        assert_eq!(props.maze[0].len(), 3);
+        assert_eq!(props.maze.height(), 2);
+        assert_eq!(props.maze.width(), 3);
         assert_eq!(props.pacman_pos.x, 0);
         assert_eq!(props.pacman_pos.y, 0);
         assert!(props.ghosts.is_empty());
         assert!(!props.is_dying);
         assert!(!props.is_invincible);
     }
+
+    #[test]
+    fn test_imported_maze_lands_on_latest_draft() {
+        let draft = Rc::new(MazeDraft::from_text("#####\n#P  #\n#####\n"));
+        let painted = draft.reduce(DraftAction::Paint(2, 1, Brush::Tile(Tile::Wall)));
+        let imported = painted.reduce(DraftAction::Load("#######\n#P    #\n#######\n".into()));
+        assert_eq!(imported.width(), 7);
+
+        let undone = imported.reduce(DraftAction::Undo);
+        assert_eq!(undone.symbol(2, 1), '#');
+        let undone = undone.reduce(DraftAction::Undo);
+        assert_eq!(undone.symbol(2, 1), ' ');
+        assert!(!undone.can_undo());
+    }
+
+    /// Walls the board paints in the house colours.
+    fn house_coloured_walls(maze: &Maze, theme: &MazeTheme) -> Vec<(usize, usize)> {
+        let house = house_band(maze);
+        (0..maze.height())
+            .flat_map(|y| (0..maze.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                wall_style(maze, theme, house.as_ref(), x, y) == Some(theme.wall_style(true))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_house_colours_follow_a_top_door_house() {
+        let pack = &MAZE_PACKS[1];
+        let crossroads = pack
+            .mazes
+            .iter()
+            .find(|maze| maze.name == "Crossroads")
+            .expect("Crossroads is in the Grand Tour");
+        let maze = parse_maze(crossroads.text).unwrap();
+        let door = (0..maze.width())
+            .find_map(|x| (0..maze.height()).find(|&y| maze.tiles[y][x] == Tile::GhostDoor))
+            .unwrap();
+        assert!(door < maze.ghost_spawns[0].y, "door should be on top");
+
+        // The house is `###-###` over `# GGG #` over `#######`.
+        let mut expected: Vec<(usize, usize)> = (14..=20)
+            .flat_map(|x| [(x, 8), (x, 10)])
+            .filter(|&(x, y)| maze.tiles[y][x] == Tile::Wall)
+            .chain([(14, 9), (20, 9)])
+            .collect();
+        expected.sort_by_key(|&(x, y)| (y, x));
+        assert_eq!(expected.len(), 15);
+
+        assert_eq!(house_coloured_walls(&maze, &crossroads.theme), expected);
+    }
+
+    #[test]
+    fn test_house_colours_follow_the_classic_house() {
+        let maze = Maze::classic();
+        // Open-topped, `#     #` over `# GGG #` over `###-###`.
+        assert_eq!(
+            house_coloured_walls(&maze, &MazeTheme::default()),
+            vec![
+                (13, 9),
+                (19, 9),
+                (13, 10),
+                (19, 10),
+                (13, 11),
+                (14, 11),
+                (15, 11),
+                (17, 11),
+                (18, 11),
+                (19, 11),
+            ]
+        );
+    }
 }
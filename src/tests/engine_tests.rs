@@ -0,0 +1,588 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::game::{ticks_from_ms, FRUIT_MS};
+    use crate::engine::{
+        ghost_speed, pacman_speed, subtiles_per_tick, BonusFruit, ExtraLifeRule, GameEvent,
+        GamePhase, GameState, Input, LevelSpec, Timer, TimerWheel,
+    };
+    use crate::game_logic::pacman_render_position;
+    use crate::maze::{Maze, Tile};
+    use crate::models::{Direction, Fruit, Ghost, GhostId, GhostMode, Motion, Position};
+    use crate::tests::maze_from;
+
+    fn create_test_state() -> GameState {
+        let mut state =
+            GameState::from_maze(maze_from(&["#####", "#P.o#", "#.#.#", "#...#", "#####"]), 1);
+        state.ghosts = vec![];
+        state.start();
+        state
+    }
+
+    fn run_until(state: &mut GameState, event: &GameEvent, max_ticks: u32) -> bool {
+        (0..max_ticks).any(|_| state.step(Input::default()).contains(event))
+    }
+
+    #[test]
+    fn test_step_does_nothing_before_start() {
+        let mut state = GameState::new(1);
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+
+        assert!(events.is_empty());
+        assert_eq!(state.phase, GamePhase::NotStarted);
+        assert_eq!(state.tick, 0);
+    }
+
+    #[test]
+    fn test_step_moves_pacman_and_eats_dot() {
+        let mut state = create_test_state();
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+
+        assert_eq!(state.pacman_pos, Position { x: 2, y: 1 });
+        assert_eq!(state.score, 10);
+        assert_eq!(state.maze.tiles[1][2], Tile::Empty);
+        assert_eq!(events, vec![GameEvent::DotEaten(Position { x: 2, y: 1 })]);
+    }
+
+    #[test]
+    fn test_direction_persists_between_ticks() {
+        let mut state = create_test_state();
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        state.step(Input::default());
+
+        assert_eq!(state.pacman_pos, Position { x: 3, y: 1 });
+    }
+
+    #[test]
+    fn test_power_pellet_grants_and_expires_invincibility() {
+        let mut state = create_test_state();
+        state.pacman_pos = Position { x: 2, y: 1 };
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+
+        assert!(events.contains(&GameEvent::PowerPelletEaten(Position { x: 3, y: 1 })));
+        assert!(state.is_invincible());
+        assert!(run_until(&mut state, &GameEvent::PowerPelletExpired, 100));
+        assert!(!state.is_invincible());
+    }
+
+    #[test]
+    fn test_timer_fires_once_when_it_runs_out() {
+        let mut timers = TimerWheel::default();
+        timers.start(Timer::Fruit, 2);
+
+        assert!(timers.advance().is_empty());
+        assert_eq!(timers.remaining(Timer::Fruit), 1);
+        assert_eq!(timers.advance(), vec![Timer::Fruit]);
+        assert_eq!(timers.remaining(Timer::Fruit), 0);
+        assert!(timers.advance().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_or_restarted_timer_leaves_nothing_stale() {
+        let mut timers = TimerWheel::default();
+        timers.start(Timer::Fruit, 1);
+        timers.cancel(Timer::Fruit);
+        assert!(timers.advance().is_empty());
+
+        timers.start(Timer::Phase, 1);
+        timers.start(Timer::Phase, 3);
+        assert!(timers.advance().is_empty());
+        assert_eq!(timers.remaining(Timer::Phase), 2);
+    }
+
+    #[test]
+    fn test_paused_timer_holds_until_started_again() {
+        let mut timers = TimerWheel::default();
+        timers.start(Timer::PowerPellet, 1);
+        timers.start(Timer::Phase, 2);
+        timers.pause(Timer::PowerPellet);
+
+        assert!(timers.advance().is_empty());
+        assert_eq!(timers.remaining(Timer::PowerPellet), 1);
+        assert_eq!(timers.remaining(Timer::Phase), 1);
+
+        timers.start(Timer::PowerPellet, 1);
+        assert_eq!(timers.advance(), vec![Timer::Phase, Timer::PowerPellet]);
+    }
+
+    #[test]
+    fn test_resumed_timer_carries_on_where_it_was_held() {
+        let mut timers = TimerWheel::default();
+        timers.start(Timer::Fruit, 3);
+        timers.advance();
+        timers.pause(Timer::Fruit);
+        assert!(timers.running().is_empty());
+
+        for _ in 0..5 {
+            assert!(timers.advance().is_empty());
+        }
+        assert_eq!(timers.remaining(Timer::Fruit), 2);
+
+        timers.resume(Timer::Fruit);
+        assert_eq!(timers.running(), vec![Timer::Fruit]);
+        timers.advance();
+        assert_eq!(timers.remaining(Timer::Fruit), 1);
+        assert_eq!(timers.advance(), vec![Timer::Fruit]);
+    }
+
+    #[test]
+    fn test_paused_game_stands_still() {
+        let mut state = create_test_state();
+        state.pacman_pos = Position { x: 2, y: 1 };
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert!(state.is_invincible());
+
+        state.pause();
+        assert!(state.is_paused());
+        let frozen = state.clone();
+        for _ in 0..100 {
+            assert!(state.step(Input::default()).is_empty());
+        }
+        assert!(state == frozen);
+
+        state.resume();
+        assert!(run_until(&mut state, &GameEvent::PowerPelletExpired, 100));
+    }
+
+    #[test]
+    fn test_game_can_only_be_paused_in_progress() {
+        let mut state = GameState::new(1);
+        state.pause();
+        assert!(!state.is_paused());
+
+        state.start();
+        state.pause();
+        assert!(state.is_paused());
+        state.restart(2);
+        assert!(!state.is_paused());
+    }
+
+    #[test]
+    fn test_collision_loses_life_then_respawns() {
+        let mut state = create_test_state();
+        state.ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 1 },
+            Position { x: 0, y: 0 },
+        )];
+
+        let events = state.step(Input::default());
+        assert_eq!(events, vec![GameEvent::PacmanCaught]);
+        assert_eq!(state.lives, 2);
+        assert_eq!(state.phase, GamePhase::Dying);
+        assert!(state.is_dying());
+
+        assert!(run_until(&mut state, &GameEvent::Respawned, 100));
+        assert_eq!(state.phase, GamePhase::Playing);
+        assert_eq!(state.direction, Direction::None);
+    }
+
+    #[test]
+    fn test_turn_queued_while_respawning_is_taken() {
+        let mut state = create_test_state();
+        state.ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 1 },
+            Position { x: 0, y: 0 },
+        )];
+        state.step(Input::default());
+        while state.phase == GamePhase::Dying {
+            state.step(Input::default());
+        }
+
+        state.step(Input {
+            direction: Some(Direction::Down),
+        });
+        assert!(run_until(&mut state, &GameEvent::Respawned, 100));
+        assert_eq!(state.queued_direction, Some(Direction::Down));
+
+        state.step(Input::default());
+        assert_eq!(state.direction, Direction::Down);
+    }
+
+    #[test]
+    fn test_last_life_ends_game() {
+        let mut state = create_test_state();
+        state.lives = 1;
+        state.ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 1 },
+            Position { x: 0, y: 0 },
+        )];
+
+        state.step(Input::default());
+        assert!(run_until(&mut state, &GameEvent::GameOver, 100));
+        assert_eq!(state.phase, GamePhase::GameOver);
+        assert_eq!(state.lives, 0);
+    }
+
+    fn clear_maze(state: &mut GameState) {
+        for tile in state.maze.tiles.iter_mut().flatten() {
+            if *tile != Tile::Wall {
+                *tile = Tile::Empty;
+            }
+        }
+    }
+
+    #[test]
+    fn test_cleared_maze_completes_level() {
+        let mut state = create_test_state();
+        clear_maze(&mut state);
+
+        assert_eq!(
+            state.step(Input::default()),
+            vec![GameEvent::LevelCleared(1)]
+        );
+        assert_eq!(state.phase, GamePhase::LevelComplete);
+        assert!(!state.is_over());
+    }
+
+    #[test]
+    fn test_next_level_refills_maze_and_keeps_progress() {
+        let mut state = create_test_state();
+        let full_maze = state.maze.clone();
+        state.score = 1230;
+        state.lives = 2;
+        clear_maze(&mut state);
+
+        state.step(Input::default());
+        while state.phase == GamePhase::LevelComplete {
+            state.step(Input::default());
+        }
+
+        assert_eq!(state.phase, GamePhase::Playing);
+        assert_eq!(state.level, 2);
+        assert_eq!(state.maze, full_maze);
+        assert_eq!(state.score, 1230);
+        assert_eq!(state.lives, 2);
+    }
+
+    #[test]
+    fn test_levels_cycle_through_mazes() {
+        let first = maze_from(&["#####", "#P.o#", "#.#.#", "#...#", "#####"]);
+        let second = maze_from(&["#####", "#.P.#", "#o#.#", "#...#", "#####"]);
+        let mut state = GameState::from_mazes(vec![first.clone(), second.clone()], 1);
+        state.start();
+
+        for (level, maze) in [(2, &second), (3, &first)] {
+            state.ghosts = vec![];
+            clear_maze(&mut state);
+            state.step(Input::default());
+            while state.phase == GamePhase::LevelComplete {
+                state.step(Input::default());
+            }
+            assert_eq!(state.level, level);
+            assert_eq!(state.layout(), maze);
+            assert_eq!(state.maze, *maze);
+            assert_eq!(state.pacman_pos, maze.pacman_spawn);
+        }
+    }
+
+    #[test]
+    fn test_level_table_gets_harder() {
+        let first = LevelSpec::for_level(1);
+        let later = LevelSpec::for_level(5);
+        assert!(later.ghost_speed > first.ghost_speed);
+        assert!(later.frightened_ms < first.frightened_ms);
+        assert_eq!(LevelSpec::for_level(100), LevelSpec::for_level(21));
+    }
+
+    #[test]
+    fn test_motion_accumulates_sub_tile_progress() {
+        let mut motion = Motion::default();
+        let half = subtiles_per_tick(50);
+
+        assert_eq!(motion.advance(half), 0);
+        assert_eq!(motion.fraction(), 0.5);
+        assert_eq!(motion.advance(half), 1);
+        assert_eq!(motion.fraction(), 0.0);
+
+        let steps: u32 = (0..20).map(|_| motion.advance(subtiles_per_tick(70))).sum();
+        assert_eq!(steps, 14);
+    }
+
+    #[test]
+    fn test_slower_pacman_glides_between_tiles() {
+        let mut state = create_test_state();
+        state.level = 21;
+        state.direction = Direction::Right;
+
+        state.step(Input::default());
+        assert_eq!(state.pacman_pos, Position { x: 1, y: 1 });
+        assert!(state.pacman_motion.progress > 0);
+
+        let (x, y) = pacman_render_position(
+            &state.pacman_pos,
+            state.direction,
+            &state.pacman_motion,
+            &state.maze,
+        );
+        assert!(x > 1.0 && x < 2.0);
+        assert_eq!(y, 1.0);
+    }
+
+    #[test]
+    fn test_blocked_pacman_loses_partial_progress() {
+        let mut state = create_test_state();
+        state.direction = Direction::Up;
+        state.step(Input::default());
+
+        assert_eq!(state.pacman_pos, Position { x: 1, y: 1 });
+        assert_eq!(state.pacman_motion, Motion::default());
+    }
+
+    #[test]
+    fn test_restart_resets_state() {
+        let mut state = create_test_state();
+        state.score = 500;
+        state.lives = 1;
+        state.restart(5);
+
+        assert_eq!(state.score, 0);
+        assert_eq!(state.lives, 3);
+        assert_eq!(state.phase, GamePhase::Playing);
+        assert_eq!(state.seed(), 5);
+        assert_eq!(state.layout().width(), 5);
+    }
+
+    #[test]
+    fn test_same_seed_and_inputs_replay_identically() {
+        let inputs = [
+            Some(Direction::Right),
+            None,
+            Some(Direction::Down),
+            None,
+            Some(Direction::Left),
+            Some(Direction::Up),
+        ];
+
+        let play = |seed: u64| {
+            let mut state = GameState::new(seed);
+            state.start();
+            for i in 0..60 {
+                state.step(Input {
+                    direction: inputs[i % inputs.len()],
+                });
+            }
+            state
+        };
+
+        let first = play(1234);
+        let second = play(1234);
+        assert!(first == second);
+        assert_eq!(first.seed(), 1234);
+    }
+
+    const CORRIDOR_START: Position = Position { x: 7, y: 6 };
+
+    /// A long corridor running right from the start tile, so Pac-Man can eat
+    /// enough dots for a fruit without turning.
+    fn create_corridor_state() -> GameState {
+        let mut tiles = vec![vec![Tile::Wall; 200]; 8];
+        tiles[CORRIDOR_START.y][CORRIDOR_START.x..199].fill(Tile::Dot);
+        tiles[CORRIDOR_START.y][CORRIDOR_START.x] = Tile::Empty;
+        let mut state = GameState::from_maze(
+            Maze {
+                tiles,
+                pacman_spawn: CORRIDOR_START,
+                ghost_spawns: vec![],
+                house_exit: CORRIDOR_START,
+                fruit_spawn: CORRIDOR_START,
+            },
+            1,
+        );
+        state.ghosts = vec![];
+        state.start();
+        state
+    }
+
+    #[test]
+    fn test_fruit_spawns_after_seventy_dots_and_expires() {
+        let mut state = create_corridor_state();
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert!(run_until(
+            &mut state,
+            &GameEvent::FruitSpawned(Fruit::Cherry),
+            70
+        ));
+        assert_eq!(
+            state.fruit.as_ref().map(|bonus| bonus.position.clone()),
+            Some(CORRIDOR_START)
+        );
+
+        assert!(run_until(
+            &mut state,
+            &GameEvent::FruitExpired,
+            ticks_from_ms(FRUIT_MS)
+        ));
+        assert!(state.fruit.is_none());
+    }
+
+    #[test]
+    fn test_eating_fruit_scores_level_value() {
+        let mut state = create_test_state();
+        state.level = 3;
+        state.fruit = Some(BonusFruit {
+            fruit: Fruit::Orange,
+            position: Position { x: 2, y: 1 },
+        });
+
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+
+        assert!(events.contains(&GameEvent::FruitEaten {
+            fruit: Fruit::Orange,
+            points: 500,
+        }));
+        assert_eq!(state.score, 510);
+        assert_eq!(state.collected_fruit, vec![Fruit::Orange]);
+        assert!(state.fruit.is_none());
+    }
+
+    #[test]
+    fn test_fruit_values_by_level() {
+        assert_eq!(LevelSpec::for_level(1).fruit, Fruit::Cherry);
+        assert_eq!(LevelSpec::for_level(2).fruit.points(), 300);
+        assert_eq!(LevelSpec::for_level(13).fruit, Fruit::Key);
+        assert_eq!(LevelSpec::for_level(40).fruit.points(), 5000);
+    }
+
+    #[test]
+    fn test_extra_life_awarded_once_at_threshold() {
+        let mut state = create_test_state();
+        state.score = 9_995;
+
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert!(events.contains(&GameEvent::ExtraLife));
+        assert_eq!(state.lives, 4);
+
+        state.score = 19_995;
+        let events = state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert!(!events.contains(&GameEvent::ExtraLife));
+        assert_eq!(state.lives, 4);
+    }
+
+    #[test]
+    fn test_extra_life_threshold_is_configurable() {
+        let mut state = create_test_state();
+        state.extra_life = ExtraLifeRule {
+            threshold: 50,
+            repeating: true,
+        };
+        state.score = 45;
+
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert_eq!(state.lives, 4);
+
+        state.restart(2);
+        assert_eq!(state.extra_life.threshold, 50);
+    }
+
+    #[test]
+    fn test_repeating_extra_lives() {
+        let once = ExtraLifeRule::default();
+        let repeating = ExtraLifeRule {
+            threshold: 10_000,
+            repeating: true,
+        };
+
+        assert_eq!(once.lives_earned(9_990, 10_000), 1);
+        assert_eq!(once.lives_earned(19_990, 20_000), 0);
+        assert_eq!(repeating.lives_earned(19_990, 20_000), 1);
+        assert_eq!(repeating.lives_earned(9_000, 31_000), 3);
+        assert_eq!(repeating.lives_earned(10_000, 10_010), 0);
+    }
+
+    #[test]
+    fn test_early_turn_is_queued_until_junction() {
+        let mut state = create_test_state();
+        state.direction = Direction::Right;
+        state.step(Input::default());
+        assert_eq!(state.pacman_pos, Position { x: 2, y: 1 });
+
+        // Down is blocked at (2, 1) but open one tile further on.
+        state.step(Input {
+            direction: Some(Direction::Down),
+        });
+        assert_eq!(state.pacman_pos, Position { x: 3, y: 1 });
+        assert_eq!(state.direction, Direction::Right);
+        assert_eq!(state.queued_direction, Some(Direction::Down));
+
+        state.step(Input::default());
+        assert_eq!(state.pacman_pos, Position { x: 3, y: 2 });
+        assert_eq!(state.direction, Direction::Down);
+        assert_eq!(state.queued_direction, None);
+    }
+
+    #[test]
+    fn test_open_turn_is_taken_immediately() {
+        let mut state = create_test_state();
+        state.direction = Direction::Right;
+
+        state.step(Input {
+            direction: Some(Direction::Down),
+        });
+
+        assert_eq!(state.direction, Direction::Down);
+        assert_eq!(state.pacman_pos, Position { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn test_each_actor_has_its_own_speed() {
+        let spec = LevelSpec::for_level(1);
+        let maze = maze_from(&["T  T"]);
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 0 },
+            Position { x: 0, y: 0 },
+        );
+
+        assert_eq!(ghost_speed(&ghost, &spec, &maze), spec.ghost_speed);
+        ghost.mode = GhostMode::Frightened;
+        assert_eq!(
+            ghost_speed(&ghost, &spec, &maze),
+            spec.ghost_frightened_speed
+        );
+        ghost.position = Position { x: 0, y: 0 };
+        assert_eq!(ghost_speed(&ghost, &spec, &maze), spec.ghost_tunnel_speed);
+        ghost.mode = GhostMode::Eaten;
+        assert_eq!(ghost_speed(&ghost, &spec, &maze), 100);
+
+        assert_eq!(pacman_speed(&spec, false), spec.pacman_speed);
+        assert_eq!(pacman_speed(&spec, true), spec.pacman_frightened_speed);
+    }
+
+    #[test]
+    fn test_frightened_ghosts_slow_down() {
+        let mut state = create_test_state();
+        state.ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 3 },
+            Position { x: 3, y: 3 },
+        )];
+        state.ghosts[0].mode = GhostMode::Frightened;
+        state.ghosts[0].direction = Direction::Right;
+
+        state.step(Input::default());
+        let expected = subtiles_per_tick(LevelSpec::for_level(1).ghost_frightened_speed);
+        assert_eq!(state.ghosts[0].motion.progress, expected);
+    }
+}
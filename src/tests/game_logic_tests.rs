@@ -1,23 +1,32 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod game_logic_tests {
-    use crate::controls::get_direction_from_key;
+    use crate::constants::game::SUBTILES_PER_TILE;
+    use crate::controls::{get_direction_from_key, is_pause_key};
+    use crate::engine::{ghost_speed, subtiles_per_tick, GameRng, LevelSpec};
     use crate::game_logic::*;
-    use crate::models::{Direction, Ghost, Position};
+    use crate::maze::{Maze, Tile};
+    use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+    use crate::query::parse_seed;
+    use crate::tests::{maze_from, step_ghosts};
 
     fn simulate_move(
         direction: &Direction,
         current_pos: &Position,
-        maze: &mut Vec<Vec<u8>>,
+        maze: &mut Maze,
         score: &mut i32,
     ) -> Option<(Position, bool)> {
         let mut new_pos = current_pos.clone();
+        let tiles = &maze.tiles;
 
         let can_move = match direction {
-            Direction::Up => new_pos.y > 0 && maze[new_pos.y - 1][new_pos.x] != 1,
-            Direction::Down => new_pos.y < maze.len() - 1 && maze[new_pos.y + 1][new_pos.x] != 1,
-            Direction::Left => new_pos.x > 0 && maze[new_pos.y][new_pos.x - 1] != 1,
+            Direction::Up => new_pos.y > 0 && tiles[new_pos.y - 1][new_pos.x] != Tile::Wall,
+            Direction::Down => {
+                new_pos.y < tiles.len() - 1 && tiles[new_pos.y + 1][new_pos.x] != Tile::Wall
+            }
+            Direction::Left => new_pos.x > 0 && tiles[new_pos.y][new_pos.x - 1] != Tile::Wall,
             Direction::Right => {
-                new_pos.x < maze[0].len() - 1 && maze[new_pos.y][new_pos.x + 1] != 1
+                new_pos.x < tiles[0].len() - 1 && tiles[new_pos.y][new_pos.x + 1] != Tile::Wall
             }
             Direction::None => false,
         };
@@ -37,14 +46,8 @@ mod game_logic_tests {
         }
     }
 
-    fn create_test_maze() -> Vec<Vec<u8>> {
-        vec![
-            vec![1, 1, 1, 1, 1],
-            vec![1, 0, 2, 0, 1],
-            vec![1, 2, 3, 2, 1],
-            vec![1, 0, 2, 0, 1],
-            vec![1, 1, 1, 1, 1],
-        ]
+    fn create_test_maze() -> Maze {
+        maze_from(&["#####", "# . #", "#.o.#", "# . #", "#####"])
     }
 
     #[test]
@@ -56,6 +59,64 @@ mod game_logic_tests {
         assert_eq!(get_direction_from_key("Invalid"), None);
     }
 
+    #[test]
+    fn test_pause_keys() {
+        assert!(is_pause_key("p"));
+        assert!(is_pause_key("P"));
+        assert!(is_pause_key("Escape"));
+        assert!(!is_pause_key("ArrowUp"));
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("?seed=42"), Some(42));
+        assert_eq!(parse_seed("?level=2&seed=7"), Some(7));
+        assert_eq!(parse_seed("?seed=abc"), None);
+        assert_eq!(parse_seed(""), None);
+    }
+
+    #[test]
+    fn test_move_ghosts_is_deterministic_for_seed() {
+        let maze = create_test_maze();
+        let pacman_pos = Position { x: 3, y: 3 };
+        let mut ghosts = vec![
+            Ghost::new(
+                GhostId::Clyde,
+                Position { x: 1, y: 1 },
+                Position { x: 0, y: 0 },
+            ),
+            Ghost::new(
+                GhostId::Inky,
+                Position { x: 3, y: 1 },
+                Position { x: 0, y: 0 },
+            ),
+        ];
+        for ghost in ghosts.iter_mut() {
+            ghost.mode = GhostMode::Chase;
+        }
+
+        let run = |seed: u64| {
+            let mut rng = GameRng::new(seed);
+            let mut ghosts = ghosts.clone();
+            let mut trail = Vec::new();
+            for _ in 0..20 {
+                step_ghosts(
+                    &mut ghosts,
+                    &pacman_pos,
+                    Direction::None,
+                    None,
+                    &maze,
+                    &[SUBTILES_PER_TILE; 4],
+                    &mut rng,
+                );
+                trail.extend(ghosts.iter().map(|g| g.position.clone()));
+            }
+            trail
+        };
+
+        assert_eq!(run(99), run(99));
+    }
+
     #[test]
     fn test_valid_ghost_moves() {
         let maze = create_test_maze();
@@ -72,18 +133,181 @@ mod game_logic_tests {
     fn test_ghost_movement() {
         let maze = create_test_maze();
         let pacman_pos = Position { x: 3, y: 1 };
-        let ghost = Ghost {
-            position: Position { x: 1, y: 1 },
-            color: "#FF0000",
-        };
+        let ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 1 },
+            Position { x: 0, y: 0 },
+        );
 
-        let next_move = find_ghost_move(&ghost, &pacman_pos, &maze, true);
+        let next_move = find_ghost_move(&ghost, &pacman_pos, &maze);
         assert!(next_move.is_some());
         if let Some(new_pos) = next_move {
             assert!(new_pos.x > ghost.position.x);
         }
     }
 
+    /// A plus-shaped junction at (2, 2) with an arm in every direction.
+    fn create_junction_maze() -> Maze {
+        maze_from(&["## ##", "## ##", "     ", "## ##", "## ##"])
+    }
+
+    fn ghost_heading(direction: Direction, position: Position) -> Ghost {
+        let mut ghost = Ghost::new(GhostId::Blinky, position, Position { x: 0, y: 0 });
+        ghost.mode = GhostMode::Chase;
+        ghost.direction = direction;
+        ghost
+    }
+
+    #[test]
+    fn test_ghost_never_turns_back_in_a_corridor() {
+        let maze = maze_from(&["#####", "#   #", "#####"]);
+        let ghost = ghost_heading(Direction::Right, Position { x: 2, y: 1 });
+
+        assert_eq!(
+            get_ghost_moves(&ghost, &maze),
+            vec![Position { x: 3, y: 1 }]
+        );
+        // Even with the target behind it, it keeps going.
+        assert_eq!(
+            find_ghost_move(&ghost, &Position { x: 1, y: 1 }, &maze),
+            Some(Position { x: 3, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_ghost_reverses_only_in_dead_end() {
+        let maze = maze_from(&["#####", "#   #", "#####"]);
+        let ghost = ghost_heading(Direction::Right, Position { x: 3, y: 1 });
+
+        assert_eq!(
+            get_ghost_moves(&ghost, &maze),
+            vec![Position { x: 2, y: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_ghost_moves_are_in_tie_break_order() {
+        let maze = create_junction_maze();
+        let center = Position { x: 2, y: 2 };
+        let up = Position { x: 2, y: 1 };
+        let left = Position { x: 1, y: 2 };
+        let down = Position { x: 2, y: 3 };
+        let right = Position { x: 3, y: 2 };
+
+        let ghost = ghost_heading(Direction::None, center.clone());
+        assert_eq!(
+            get_ghost_moves(&ghost, &maze),
+            vec![up.clone(), left.clone(), down.clone(), right.clone()]
+        );
+        let ghost = ghost_heading(Direction::Left, center);
+        assert_eq!(get_ghost_moves(&ghost, &maze), vec![up, left, down]);
+    }
+
+    #[test]
+    fn test_ties_break_up_left_down_right() {
+        let maze = create_junction_maze();
+        let center = Position { x: 2, y: 2 };
+        let cases = [
+            // Up and Left are both one tile from the target.
+            (
+                Direction::Up,
+                Position { x: 1, y: 1 },
+                Position { x: 2, y: 1 },
+            ),
+            // Left and Down.
+            (
+                Direction::Left,
+                Position { x: 1, y: 3 },
+                Position { x: 1, y: 2 },
+            ),
+            // Down and Right.
+            (
+                Direction::Down,
+                Position { x: 3, y: 3 },
+                Position { x: 2, y: 3 },
+            ),
+        ];
+
+        for (heading, target, expected) in cases {
+            let ghost = ghost_heading(heading, center.clone());
+            assert_eq!(find_ghost_move(&ghost, &target, &maze), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_frightened_ghost_decides_only_at_junctions() {
+        let maze = maze_from(&["#######", "#     #", "#######"]);
+        let mut ghost = ghost_heading(Direction::Left, Position { x: 4, y: 1 });
+        ghost.mode = GhostMode::Frightened;
+        let mut ghosts = vec![ghost];
+        let mut rng = GameRng::new(3);
+
+        for _ in 0..3 {
+            step_ghosts(
+                &mut ghosts,
+                &Position { x: 0, y: 0 },
+                Direction::None,
+                None,
+                &maze,
+                &[SUBTILES_PER_TILE],
+                &mut rng,
+            );
+        }
+        assert_eq!(ghosts[0].position, Position { x: 1, y: 1 });
+        assert!(rng == GameRng::new(3), "no random turns in a corridor");
+    }
+
+    fn open_maze(width: usize, height: usize) -> Maze {
+        Maze {
+            tiles: vec![vec![Tile::Empty; width]; height],
+            ..maze_from(&[" "])
+        }
+    }
+
+    #[test]
+    fn test_pinky_targets_four_tiles_ahead() {
+        let maze = open_maze(20, 20);
+        let pacman_pos = Position { x: 10, y: 10 };
+
+        let target = ambush_target(&pacman_pos, Direction::Left, &maze);
+        assert_eq!(target, Position { x: 6, y: 10 });
+
+        let target = ambush_target(&pacman_pos, Direction::Down, &maze);
+        assert_eq!(target, Position { x: 10, y: 14 });
+    }
+
+    #[test]
+    fn test_inky_doubles_vector_from_blinky() {
+        let maze = open_maze(20, 20);
+        let pacman_pos = Position { x: 10, y: 10 };
+        let blinky_pos = Position { x: 8, y: 8 };
+
+        // Pivot is two tiles right of Pac-Man at (12, 10); doubling (8, 8) -> (12, 10) lands on (16, 12).
+        let target = flank_target(&pacman_pos, Direction::Right, &blinky_pos, &maze);
+        assert_eq!(target, Position { x: 16, y: 12 });
+    }
+
+    #[test]
+    fn test_targets_are_clamped_to_maze() {
+        let maze = open_maze(20, 20);
+        let pacman_pos = Position { x: 1, y: 1 };
+
+        let target = ambush_target(&pacman_pos, Direction::Up, &maze);
+        assert_eq!(target, Position { x: 1, y: 0 });
+    }
+
+    #[test]
+    fn test_clyde_retreats_when_close() {
+        let pacman_pos = Position { x: 10, y: 10 };
+        let corner = Position { x: 0, y: 19 };
+
+        let far = Ghost::new(GhostId::Clyde, Position { x: 10, y: 1 }, corner.clone());
+        assert_eq!(shy_target(&far, &pacman_pos), pacman_pos);
+
+        let near = Ghost::new(GhostId::Clyde, Position { x: 10, y: 5 }, corner.clone());
+        assert_eq!(shy_target(&near, &pacman_pos), corner);
+    }
+
     #[test]
     fn test_score_update() {
         let mut maze = create_test_maze();
@@ -92,23 +316,24 @@ mod game_logic_tests {
 
         let power_pellet = update_score(&pos, &mut maze, &mut score);
         assert_eq!(score, 10);
-        assert_eq!(maze[pos.y][pos.x], 0);
+        assert_eq!(maze.tile(&pos), Tile::Empty);
         assert!(!power_pellet);
 
         let power_pos = Position { x: 2, y: 2 };
         let power_pellet = update_score(&power_pos, &mut maze, &mut score);
         assert_eq!(score, 60);
-        assert_eq!(maze[power_pos.y][power_pos.x], 0);
+        assert_eq!(maze.tile(&power_pos), Tile::Empty);
         assert!(power_pellet);
     }
 
     #[test]
     fn test_ghost_collision() {
         let pacman_pos = Position { x: 2, y: 2 };
-        let ghosts = vec![Ghost {
-            position: Position { x: 2, y: 2 },
-            color: "#FF0000",
-        }];
+        let ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 2, y: 2 },
+            Position { x: 0, y: 0 },
+        )];
 
         let mut is_dying = false;
         let mut lives = 3;
@@ -171,14 +396,120 @@ mod game_logic_tests {
         let mut maze = create_test_maze();
         assert!(!check_game_complete(&maze));
 
-        for row in maze.iter_mut() {
-            for cell in row.iter_mut() {
-                if *cell == 2 || *cell == 3 {
-                    *cell = 0;
-                }
+        for tile in maze.tiles.iter_mut().flatten() {
+            if tile.is_food() {
+                *tile = Tile::Empty;
             }
         }
 
         assert!(check_game_complete(&maze));
     }
+
+    fn create_tunnel_maze() -> Maze {
+        maze_from(&["#######", "T     T", "# ### #", "#######"])
+    }
+
+    #[test]
+    fn test_pacman_wraps_through_tunnel() {
+        let mut maze = create_tunnel_maze();
+        let mut score = 0;
+
+        let (pos, _) = calculate_next_position(
+            &Direction::Left,
+            &Position { x: 0, y: 1 },
+            &mut maze,
+            &mut score,
+        )
+        .unwrap();
+        assert_eq!(pos, Position { x: 6, y: 1 });
+
+        let (pos, _) = calculate_next_position(
+            &Direction::Right,
+            &Position { x: 6, y: 1 },
+            &mut maze,
+            &mut score,
+        )
+        .unwrap();
+        assert_eq!(pos, Position { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn test_edge_without_tunnel_is_closed() {
+        let mut maze = open_maze(3, 3);
+        let mut score = 0;
+        assert!(calculate_next_position(
+            &Direction::Left,
+            &Position { x: 0, y: 1 },
+            &mut maze,
+            &mut score
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_ghost_moves_and_paths_wrap() {
+        let maze = create_tunnel_maze();
+        let moves = get_valid_ghost_moves(&Position { x: 0, y: 1 }, &maze);
+        assert!(moves.contains(&Position { x: 6, y: 1 }));
+
+        assert_eq!(
+            find_path_move(&Position { x: 1, y: 1 }, &Position { x: 6, y: 1 }, &maze),
+            Some(Position { x: 0, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_ghost_chases_through_tunnel() {
+        let maze = create_tunnel_maze();
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 1, y: 1 },
+            Position { x: 0, y: 0 },
+        );
+        ghost.mode = GhostMode::Chase;
+
+        assert_eq!(
+            find_ghost_move(&ghost, &Position { x: 5, y: 1 }, &maze),
+            Some(Position { x: 0, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_ghosts_are_slowed_in_tunnel() {
+        let maze = create_tunnel_maze();
+        let mut rng = GameRng::new(1);
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 0, y: 1 },
+            Position { x: 0, y: 0 },
+        );
+        ghost.mode = GhostMode::Chase;
+        ghost.direction = Direction::Left;
+        let mut ghosts = vec![ghost];
+        let pacman_pos = Position { x: 4, y: 1 };
+        let spec = LevelSpec {
+            ghost_speed: 100,
+            ghost_tunnel_speed: 50,
+            ..LevelSpec::for_level(1)
+        };
+
+        let mut moves = |ghosts: &mut Vec<Ghost>| {
+            let speed = subtiles_per_tick(ghost_speed(&ghosts[0], &spec, &maze));
+            step_ghosts(
+                ghosts,
+                &pacman_pos,
+                Direction::None,
+                None,
+                &maze,
+                &[speed],
+                &mut rng,
+            );
+            ghosts[0].position.clone()
+        };
+        assert_eq!(moves(&mut ghosts), Position { x: 0, y: 1 });
+        assert_eq!(moves(&mut ghosts), Position { x: 6, y: 1 });
+        assert_eq!(moves(&mut ghosts), Position { x: 6, y: 1 });
+        assert_eq!(moves(&mut ghosts), Position { x: 5, y: 1 });
+        assert_eq!(ghosts[0].direction, Direction::Left);
+    }
 }
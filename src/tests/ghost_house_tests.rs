@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::game::ticks_from_ms;
+    use crate::engine::GhostHouse;
+    use crate::models::{Ghost, GhostId, GhostMode, Position};
+
+    fn waiting_ghosts() -> Vec<Ghost> {
+        [GhostId::Pinky, GhostId::Inky, GhostId::Clyde]
+            .into_iter()
+            .map(|id| Ghost::in_house(id, Position { x: 3, y: 3 }, Position { x: 0, y: 0 }))
+            .collect()
+    }
+
+    fn release(ghosts: &mut [Ghost], id: GhostId) {
+        if let Some(ghost) = ghosts.iter_mut().find(|ghost| ghost.id == id) {
+            ghost.mode = GhostMode::Leaving;
+        }
+    }
+
+    #[test]
+    fn test_pinky_leaves_immediately() {
+        let mut house = GhostHouse::new(1);
+        assert_eq!(house.tick(&waiting_ghosts()), Some(GhostId::Pinky));
+    }
+
+    #[test]
+    fn test_inky_waits_for_dot_limit_on_level_one() {
+        let mut house = GhostHouse::new(1);
+        let mut ghosts = waiting_ghosts();
+        release(&mut ghosts, GhostId::Pinky);
+
+        for _ in 0..29 {
+            house.on_dot_eaten(&ghosts);
+            assert_eq!(house.tick(&ghosts), None);
+        }
+        house.on_dot_eaten(&ghosts);
+        assert_eq!(house.tick(&ghosts), Some(GhostId::Inky));
+    }
+
+    #[test]
+    fn test_idle_timer_forces_release() {
+        let mut house = GhostHouse::new(1);
+        let mut ghosts = waiting_ghosts();
+        release(&mut ghosts, GhostId::Pinky);
+
+        let limit = ticks_from_ms(4000);
+        for _ in 1..limit {
+            assert_eq!(house.tick(&ghosts), None);
+        }
+        assert_eq!(house.tick(&ghosts), Some(GhostId::Inky));
+    }
+
+    #[test]
+    fn test_global_counter_after_life_lost() {
+        let mut house = GhostHouse::new(1);
+        let ghosts = waiting_ghosts();
+        house.on_life_lost();
+
+        assert_eq!(house.tick(&ghosts), None);
+        for _ in 0..7 {
+            house.on_dot_eaten(&ghosts);
+        }
+        assert_eq!(house.tick(&ghosts), Some(GhostId::Pinky));
+    }
+
+    #[test]
+    fn test_later_levels_release_everyone_at_once() {
+        let mut house = GhostHouse::new(3);
+        let mut ghosts = waiting_ghosts();
+        for id in [GhostId::Pinky, GhostId::Inky, GhostId::Clyde] {
+            assert_eq!(house.tick(&ghosts), Some(id));
+            release(&mut ghosts, id);
+        }
+        assert_eq!(house.tick(&ghosts), None);
+    }
+}
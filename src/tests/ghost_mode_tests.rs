@@ -0,0 +1,430 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::game::ticks_from_ms;
+    use crate::constants::game::SUBTILES_PER_TILE;
+    use crate::engine::{
+        ghost_speed, GameEvent, GamePhase, GameRng, GameState, Input, LevelSpec, ModeClock,
+    };
+    use crate::game_logic::{
+        calculate_next_position, check_ghost_collision, eat_frightened_ghosts, find_path_move,
+        get_valid_ghost_moves, ghost_points,
+    };
+    use crate::maze::{Maze, Tile};
+    use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+    use crate::tests::{maze_from, step_ghosts};
+
+    fn create_test_maze() -> Maze {
+        maze_from(&["#######", "#P    #", "# # # #", "#     #", "#######"])
+    }
+
+    fn create_test_game() -> GameState {
+        GameState::from_maze(
+            Maze {
+                house_exit: Position { x: 3, y: 1 },
+                ..create_test_maze()
+            },
+            1,
+        )
+    }
+
+    fn tick_until_change(clock: &mut ModeClock) -> (u32, GhostMode) {
+        for ticks in 1..100_000 {
+            if let Some(mode) = clock.tick() {
+                return (ticks, mode);
+            }
+        }
+        panic!("mode never changed");
+    }
+
+    #[test]
+    fn test_level_one_schedule() {
+        let mut clock = ModeClock::new(1);
+        assert_eq!(clock.mode(), GhostMode::Scatter);
+
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(7000), GhostMode::Chase)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(20000), GhostMode::Scatter)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(7000), GhostMode::Chase)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(20000), GhostMode::Scatter)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(5000), GhostMode::Chase)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(20000), GhostMode::Scatter)
+        );
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(5000), GhostMode::Chase)
+        );
+
+        for _ in 0..10_000 {
+            assert_eq!(clock.tick(), None);
+        }
+        assert_eq!(clock.mode(), GhostMode::Chase);
+    }
+
+    #[test]
+    fn test_later_levels_scatter_less() {
+        let mut clock = ModeClock::new(5);
+        assert_eq!(
+            tick_until_change(&mut clock),
+            (ticks_from_ms(5000), GhostMode::Chase)
+        );
+    }
+
+    #[test]
+    fn test_mode_change_reverses_ghost() {
+        let maze = create_test_maze();
+        let mut rng = GameRng::new(1);
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 3, y: 1 },
+            Position { x: 6, y: 0 },
+        );
+        ghost.direction = Direction::Right;
+
+        ghost.set_mode(GhostMode::Chase);
+        assert!(ghost.reverse_pending);
+
+        let mut ghosts = vec![ghost];
+        step_ghosts(
+            &mut ghosts,
+            &Position { x: 5, y: 1 },
+            Direction::None,
+            None,
+            &maze,
+            &[SUBTILES_PER_TILE; 4],
+            &mut rng,
+        );
+        assert_eq!(ghosts[0].position, Position { x: 2, y: 1 });
+        assert_eq!(ghosts[0].direction, Direction::Left);
+        assert!(!ghosts[0].reverse_pending);
+    }
+
+    #[test]
+    fn test_leaving_frightened_does_not_reverse() {
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 3, y: 1 },
+            Position { x: 6, y: 0 },
+        );
+        ghost.mode = GhostMode::Frightened;
+
+        ghost.set_mode(GhostMode::Chase);
+        assert!(!ghost.reverse_pending);
+    }
+
+    #[test]
+    fn test_scatter_heads_for_corner() {
+        let maze = create_test_maze();
+        let mut rng = GameRng::new(1);
+        let mut ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 3, y: 3 },
+            Position { x: 6, y: 4 },
+        )];
+
+        step_ghosts(
+            &mut ghosts,
+            &Position { x: 1, y: 1 },
+            Direction::None,
+            None,
+            &maze,
+            &[SUBTILES_PER_TILE; 4],
+            &mut rng,
+        );
+        assert_eq!(ghosts[0].position, Position { x: 4, y: 3 });
+    }
+
+    #[test]
+    fn test_frightened_ghost_is_eaten_not_deadly() {
+        let pacman_pos = Position { x: 1, y: 1 };
+        let mut ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            pacman_pos.clone(),
+            Position { x: 0, y: 0 },
+        )];
+        ghosts[0].mode = GhostMode::Frightened;
+
+        let met = [true];
+        assert!(!check_ghost_collision(&met, &ghosts));
+        assert_eq!(
+            eat_frightened_ghosts(&met, &mut ghosts),
+            vec![GhostId::Blinky]
+        );
+        assert_eq!(ghosts[0].mode, GhostMode::Eaten);
+        assert!(!check_ghost_collision(&met, &ghosts));
+    }
+
+    fn create_house_maze() -> Maze {
+        maze_from(&[
+            "#######", "#     #", "# #-# #", "# # # #", "# ### #", "#######",
+        ])
+    }
+
+    #[test]
+    fn test_eaten_ghost_returns_home_then_leaves() {
+        let exit = Position { x: 3, y: 1 };
+        let maze = Maze {
+            house_exit: exit.clone(),
+            ..create_house_maze()
+        };
+        let mut rng = GameRng::new(1);
+        let mut ghost = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 3, y: 3 },
+            Position { x: 6, y: 0 },
+        );
+        ghost.position = Position { x: 1, y: 1 };
+        ghost.mode = GhostMode::Eaten;
+        let mut ghosts = vec![ghost];
+        let pacman_pos = Position { x: 5, y: 4 };
+
+        for _ in 0..4 {
+            step_ghosts(
+                &mut ghosts,
+                &pacman_pos,
+                Direction::None,
+                None,
+                &maze,
+                &[SUBTILES_PER_TILE; 4],
+                &mut rng,
+            );
+        }
+        assert_eq!(ghosts[0].position, Position { x: 3, y: 3 });
+        assert_eq!(ghosts[0].mode, GhostMode::Leaving);
+
+        for _ in 0..2 {
+            step_ghosts(
+                &mut ghosts,
+                &pacman_pos,
+                Direction::None,
+                None,
+                &maze,
+                &[SUBTILES_PER_TILE; 4],
+                &mut rng,
+            );
+        }
+        assert_eq!(ghosts[0].position, exit);
+    }
+
+    #[test]
+    fn test_door_blocks_pacman_and_roaming_ghosts() {
+        let mut maze = create_house_maze();
+        let exit = Position { x: 3, y: 1 };
+
+        assert!(!get_valid_ghost_moves(&exit, &maze).contains(&Position { x: 3, y: 2 }));
+        let mut score = 0;
+        assert!(calculate_next_position(&Direction::Down, &exit, &mut maze, &mut score).is_none());
+    }
+
+    #[test]
+    fn test_power_pellet_frightens_then_restores_schedule() {
+        let mut state = create_test_game();
+        state.maze.tiles[1][2] = Tile::PowerPellet;
+        state.maze.tiles[3][1] = Tile::Dot;
+        state.pacman_pos = Position { x: 1, y: 1 };
+        state.ghosts = vec![Ghost::new(
+            GhostId::Blinky,
+            Position { x: 5, y: 3 },
+            Position { x: 6, y: 4 },
+        )];
+        state.start();
+
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        assert_eq!(state.ghosts[0].mode, GhostMode::Frightened);
+
+        for _ in 0..ticks_from_ms(5000) {
+            state.step(Input::default());
+        }
+        assert_eq!(state.ghosts[0].mode, GhostMode::Scatter);
+    }
+
+    #[test]
+    fn test_ghost_points_double_up_to_1600() {
+        let points: Vec<i32> = (0..6).map(ghost_points).collect();
+        assert_eq!(points, vec![200, 400, 800, 1600, 1600, 1600]);
+    }
+
+    #[test]
+    fn test_path_move_goes_around_walls() {
+        let maze = maze_from(&["#####", "# # #", "# # #", "#   #", "#####"]);
+
+        let step = find_path_move(&Position { x: 1, y: 1 }, &Position { x: 3, y: 1 }, &maze);
+        assert_eq!(step, Some(Position { x: 1, y: 2 }));
+        assert_eq!(
+            find_path_move(&Position { x: 1, y: 1 }, &Position { x: 1, y: 1 }, &maze),
+            None
+        );
+    }
+
+    #[test]
+    fn test_eating_ghosts_awards_combo_and_popups() {
+        let mut state = create_test_game();
+        state.maze.tiles[3][1] = Tile::Dot;
+        state.pacman_pos = Position { x: 3, y: 1 };
+        state.ghosts = [GhostId::Blinky, GhostId::Pinky, GhostId::Inky]
+            .into_iter()
+            .map(|id| {
+                let mut ghost = Ghost::new(id, Position { x: 5, y: 3 }, Position { x: 0, y: 0 });
+                ghost.position = Position { x: 3, y: 1 };
+                ghost.mode = GhostMode::Frightened;
+                ghost
+            })
+            .collect();
+        state.start();
+
+        let events = state.step(Input::default());
+        let points: Vec<i32> = events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::GhostEaten { points, .. } => Some(*points),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(points, vec![200, 400, 800]);
+        assert_eq!(state.score, 1400);
+        assert_eq!(state.popups.len(), 3);
+        assert!(state.ghosts.iter().all(|g| g.mode == GhostMode::Eaten));
+        assert_eq!(state.phase, GamePhase::Playing);
+
+        for _ in 0..ticks_from_ms(1000) {
+            state.step(Input::default());
+        }
+        assert!(state.popups.is_empty());
+    }
+
+    #[test]
+    fn test_new_power_pellet_resets_combo() {
+        let mut state = create_test_game();
+        state.maze.tiles[1][2] = Tile::PowerPellet;
+        state.maze.tiles[1][4] = Tile::PowerPellet;
+        state.maze.tiles[3][1] = Tile::Dot;
+        state.pacman_pos = Position { x: 1, y: 1 };
+        state.ghosts = vec![];
+        state.start();
+
+        state.step(Input {
+            direction: Some(Direction::Right),
+        });
+        let mut ghost = Ghost::new(
+            GhostId::Clyde,
+            Position { x: 5, y: 3 },
+            Position { x: 0, y: 0 },
+        );
+        ghost.position = Position { x: 2, y: 1 };
+        ghost.mode = GhostMode::Frightened;
+        state.ghosts = vec![ghost.clone()];
+        state.step(Input::default());
+        assert_eq!(state.score, 50 + 200);
+
+        // Pac-Man is now on (3, 1); the next pellet at (4, 1) starts a fresh combo.
+        state.step(Input::default());
+        ghost.position = Position { x: 4, y: 1 };
+        state.ghosts = vec![ghost];
+        state.step(Input {
+            direction: Some(Direction::Left),
+        });
+        assert_eq!(state.score, 50 + 200 + 50 + 200);
+    }
+
+    #[test]
+    fn test_elroy_stages_follow_remaining_dots() {
+        let spec = LevelSpec::for_level(1);
+        assert_eq!(spec.elroy_stage(spec.elroy_dots + 1), 0);
+        assert_eq!(spec.elroy_stage(spec.elroy_dots), 1);
+        assert_eq!(spec.elroy_stage(spec.elroy_dots / 2), 2);
+        assert!(LevelSpec::for_level(10).elroy_dots > spec.elroy_dots);
+    }
+
+    #[test]
+    fn test_elroy_blinky_is_faster_and_ignores_scatter() {
+        let maze = create_test_maze();
+        let spec = LevelSpec::for_level(1);
+        let mut rng = GameRng::new(1);
+        let mut blinky = Ghost::new(
+            GhostId::Blinky,
+            Position { x: 3, y: 3 },
+            Position { x: 6, y: 4 },
+        );
+        blinky.elroy = 2;
+        assert_eq!(ghost_speed(&blinky, &spec, &maze), spec.elroy_speeds[1]);
+        assert!(spec.elroy_speeds[1] > spec.ghost_speed);
+
+        let mut ghosts = vec![blinky];
+        step_ghosts(
+            &mut ghosts,
+            &Position { x: 1, y: 3 },
+            Direction::None,
+            None,
+            &maze,
+            &[SUBTILES_PER_TILE; 4],
+            &mut rng,
+        );
+        assert_eq!(ghosts[0].mode, GhostMode::Scatter);
+        assert_eq!(ghosts[0].position, Position { x: 2, y: 3 });
+    }
+
+    #[test]
+    fn test_elroy_suspended_after_life_lost_until_clyde_leaves() {
+        let mut state = create_test_game();
+        state.maze.tiles[1][5] = Tile::Dot;
+        state.pacman_pos = Position { x: 1, y: 1 };
+        state.ghosts = vec![
+            Ghost::new(
+                GhostId::Blinky,
+                Position { x: 5, y: 3 },
+                Position { x: 6, y: 4 },
+            ),
+            Ghost::in_house(
+                GhostId::Clyde,
+                Position { x: 1, y: 3 },
+                Position { x: 0, y: 4 },
+            ),
+        ];
+        state.start();
+
+        state.step(Input::default());
+        assert_eq!(state.ghosts[0].elroy, 2);
+
+        state.ghosts[0].position = state.pacman_pos.clone();
+        state.step(Input::default());
+        assert_eq!(state.phase, GamePhase::Dying);
+
+        state.ghosts = vec![
+            Ghost::new(
+                GhostId::Blinky,
+                Position { x: 5, y: 3 },
+                Position { x: 6, y: 4 },
+            ),
+            Ghost::in_house(
+                GhostId::Clyde,
+                Position { x: 1, y: 3 },
+                Position { x: 0, y: 4 },
+            ),
+        ];
+        state.phase = GamePhase::Playing;
+        state.step(Input::default());
+        assert_eq!(state.ghosts[0].elroy, 0);
+
+        state.ghosts[1].mode = GhostMode::Scatter;
+        state.step(Input::default());
+        assert_eq!(state.ghosts[0].elroy, 2);
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod tests {
+    use crate::maze::{
+        generate_maze, maze_index, parse_maze, validate_maze, Brush, Maze, MazeDraft, MazeError,
+        MazeTheme, Tile, Walker, MAZE_PACKS,
+    };
+    use crate::models::Position;
+
+    const SMALL_MAZE: &str = "\
+#########
+#P.....o#
+#.##-##.#
+#.#GGG#.#
+#.#####.#
+T.......T
+#########
+";
+
+    fn errors_for(text: &str) -> Vec<MazeError> {
+        parse_maze(text).expect_err("maze should be rejected")
+    }
+
+    #[test]
+    fn test_classic_maze_parses() {
+        let maze = Maze::classic();
+        assert_eq!((maze.width(), maze.height()), (35, 18));
+        assert_eq!(maze.pacman_spawn, Position { x: 7, y: 6 });
+        assert_eq!(maze.house_exit, Position { x: 16, y: 12 });
+        assert_eq!(maze.ghost_spawns.len(), 3);
+        assert!(validate_maze(&maze).is_empty());
+    }
+
+    #[test]
+    fn test_small_maze_parses_tiles_and_spawns() {
+        let maze = parse_maze(SMALL_MAZE).unwrap();
+        assert_eq!(maze.tiles[1][0], Tile::Wall);
+        assert_eq!(maze.tiles[1][1], Tile::Empty);
+        assert_eq!(maze.tiles[1][2], Tile::Dot);
+        assert_eq!(maze.tiles[1][7], Tile::PowerPellet);
+        assert_eq!(maze.tiles[2][4], Tile::GhostDoor);
+        assert_eq!(maze.tiles[5][0], Tile::Tunnel);
+        assert_eq!(maze.remaining_dots(), 19);
+        assert_eq!(maze.pacman_spawn, Position { x: 1, y: 1 });
+        assert_eq!(
+            maze.ghost_spawns,
+            vec![
+                Position { x: 3, y: 3 },
+                Position { x: 4, y: 3 },
+                Position { x: 5, y: 3 },
+            ]
+        );
+        assert_eq!(maze.house_exit, Position { x: 4, y: 1 });
+    }
+
+    #[test]
+    fn test_fruit_spawn_defaults_to_pacman_spawn() {
+        assert_eq!(
+            parse_maze(SMALL_MAZE).unwrap().fruit_spawn,
+            Position { x: 1, y: 1 }
+        );
+
+        let maze = parse_maze(&SMALL_MAZE.replacen(".....o", "..F..o", 1)).unwrap();
+        assert_eq!(maze.fruit_spawn, Position { x: 4, y: 1 });
+        assert_eq!(maze.tile(&maze.fruit_spawn), Tile::FruitSpawn);
+    }
+
+    #[test]
+    fn test_door_only_opens_for_house_ghosts() {
+        let maze = parse_maze(SMALL_MAZE).unwrap();
+        let door = Position { x: 4, y: 2 };
+        assert!(!maze.is_walkable_for(&door, Walker::Pacman));
+        assert!(!maze.is_walkable_for(&door, Walker::Ghost));
+        assert!(maze.is_walkable_for(&door, Walker::HouseGhost));
+
+        let exit = Position { x: 4, y: 1 };
+        assert_eq!(
+            maze.neighbors(&exit, Walker::Pacman),
+            vec![Position { x: 3, y: 1 }, Position { x: 5, y: 1 }]
+        );
+        assert_eq!(
+            maze.neighbors(&exit, Walker::HouseGhost),
+            vec![
+                Position { x: 4, y: 2 },
+                Position { x: 3, y: 1 },
+                Position { x: 5, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eating_clears_food_only() {
+        let mut maze = parse_maze(SMALL_MAZE).unwrap();
+        let pellet = Position { x: 7, y: 1 };
+        assert_eq!(maze.eat(&pellet), Some(Tile::PowerPellet));
+        assert_eq!(maze.eat(&pellet), None);
+        assert_eq!(maze.eat(&Position { x: 0, y: 0 }), None);
+        assert_eq!(maze.remaining_dots(), 18);
+    }
+
+    #[test]
+    fn test_ragged_row_reports_line_and_column() {
+        let text = SMALL_MAZE.replace("#.#####.#", "#.#####.");
+        let errors = errors_for(&text);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, Some((5, 9)));
+        assert_eq!(
+            errors[0].to_string(),
+            "line 5, column 9: row is 8 tiles wide, expected 9"
+        );
+    }
+
+    #[test]
+    fn test_unknown_tile_is_reported() {
+        let errors = errors_for(&SMALL_MAZE.replace('o', "?"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, Some((2, 8)));
+        assert_eq!(errors[0].message, "unknown tile '?'");
+    }
+
+    #[test]
+    fn test_missing_spawns_are_reported() {
+        let errors = errors_for(&SMALL_MAZE.replace('P', ".").replace("GGG", "G  "));
+        let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"no Pac-Man spawn (P)"));
+        assert!(messages.contains(&"expected 3 ghost spawns (G), found 1"));
+    }
+
+    #[test]
+    fn test_unreachable_dot_is_reported() {
+        // A dot walled in under the ghost house, only reachable through the door
+        let text = SMALL_MAZE
+            .replace("#.#####.#", "#.##.##.#")
+            .replace("T.......T", "T...#...T");
+        let errors = errors_for(&text);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, Some((5, 5)));
+        assert_eq!(
+            errors[0].message,
+            "dot cannot be reached from the Pac-Man spawn"
+        );
+    }
+
+    #[test]
+    fn test_tunnel_without_exit_is_reported() {
+        let errors = errors_for(&SMALL_MAZE.replace("T.......T", "T........"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, Some((6, 1)));
+    }
+
+    #[test]
+    fn test_maze_text_round_trips() {
+        let classic = Maze::classic();
+        assert_eq!(classic.to_string(), crate::maze::CLASSIC_MAZE);
+        assert_eq!(parse_maze(&classic.to_string()).unwrap(), classic);
+    }
+
+    #[test]
+    fn test_draft_pads_ragged_imports() {
+        let draft = MazeDraft::from_text("#####\n#P\n");
+        assert_eq!((draft.width(), draft.height()), (5, 3));
+        assert_eq!(draft.text(), "#####\n#P###\n#####\n");
+    }
+
+    #[test]
+    fn test_draft_keeps_one_pacman_spawn() {
+        let mut draft = MazeDraft::from_text(SMALL_MAZE);
+        draft.paint(3, 1, Brush::PacmanSpawn);
+        assert_eq!(draft.symbol(1, 1), ' ');
+        assert_eq!(draft.symbol(3, 1), 'P');
+        assert_eq!(
+            draft.validate().unwrap().pacman_spawn,
+            Position { x: 3, y: 1 }
+        );
+    }
+
+    #[test]
+    fn test_draft_undo_and_redo() {
+        let mut draft = MazeDraft::from_text(SMALL_MAZE);
+        assert!(!draft.can_undo());
+
+        draft.paint(2, 1, Brush::Tile(Tile::Wall));
+        draft.paint(2, 1, Brush::Tile(Tile::Wall));
+        draft.resize(12, 7);
+        assert_eq!(draft.width(), 12);
+        assert!(draft.validate().is_err());
+
+        draft.undo();
+        assert_eq!(draft.width(), 9);
+        assert_eq!(draft.symbol(2, 1), '#');
+        draft.undo();
+        assert_eq!(draft.text(), SMALL_MAZE);
+        assert!(!draft.can_undo());
+
+        draft.redo();
+        assert_eq!(draft.symbol(2, 1), '#');
+        draft.load("#####\n#P  #\n#####\n");
+        assert_eq!(draft.width(), 5);
+        draft.undo();
+        assert_eq!(draft.width(), 9);
+        draft.paint(3, 1, Brush::GhostSpawn);
+        assert!(!draft.can_redo());
+    }
+
+    #[test]
+    fn test_draft_sizes_are_clamped() {
+        let mut draft = MazeDraft::from_text(SMALL_MAZE);
+        draft.resize(0, 100);
+        assert_eq!((draft.width(), draft.height()), (3, 60));
+    }
+
+    /// Tiles `walker` can reach from `start`.
+    fn reachable_from(maze: &Maze, start: &Position, walker: Walker) -> Vec<Position> {
+        let mut seen = vec![start.clone()];
+        let mut next = 0;
+        while let Some(pos) = seen.get(next).cloned() {
+            for neighbor in maze.neighbors(&pos, walker) {
+                if !seen.contains(&neighbor) {
+                    seen.push(neighbor);
+                }
+            }
+            next += 1;
+        }
+        seen
+    }
+
+    #[test]
+    fn test_generated_maze_is_seeded() {
+        assert_eq!(generate_maze(29, 25, 7), generate_maze(29, 25, 7));
+        assert_ne!(generate_maze(29, 25, 7), generate_maze(29, 25, 8));
+    }
+
+    #[test]
+    fn test_generated_maze_sizes_are_odd_and_clamped() {
+        let maze = generate_maze(28, 21, 1);
+        assert_eq!((maze.width(), maze.height()), (27, 21));
+        let tiny = generate_maze(0, 0, 1);
+        assert_eq!((tiny.width(), tiny.height()), (15, 15));
+    }
+
+    #[test]
+    fn test_generated_mazes_are_playable() {
+        for (seed, (width, height)) in [(15, 15), (21, 17), (27, 27), (35, 31), (59, 59)]
+            .into_iter()
+            .enumerate()
+        {
+            let maze = generate_maze(width, height, seed as u64);
+            let text = maze.to_string();
+            assert_eq!(parse_maze(&text).as_ref(), Ok(&maze), "{}", text);
+
+            let last_x = maze.width() - 1;
+            for row in &maze.tiles {
+                let mirrored: Vec<Tile> = row.iter().rev().copied().collect();
+                assert_eq!(*row, mirrored, "not mirrored:\n{}", text);
+            }
+            assert!(maze.tunnel_rows().count() >= 1, "{}", text);
+            for (x, y) in [(1, 1), (last_x - 1, 1), (1, maze.height() - 2)] {
+                assert_eq!(maze.tiles[y][x], Tile::PowerPellet, "{}", text);
+            }
+
+            let reachable = reachable_from(&maze, &maze.pacman_spawn, Walker::Pacman);
+            for pos in &reachable {
+                assert!(
+                    maze.neighbors(pos, Walker::Pacman).len() >= 2,
+                    "dead end at {:?}:\n{}",
+                    pos,
+                    text
+                );
+            }
+            // Everywhere else that is open is inside the ghost house.
+            let house = reachable_from(&maze, &maze.ghost_spawns[0], Walker::Ghost);
+            let open = maze
+                .tiles
+                .iter()
+                .flatten()
+                .filter(|tile| tile.is_walkable_for(Walker::Pacman))
+                .count();
+            assert_eq!(open, reachable.len() + house.len(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_bundled_packs_parse() {
+        assert_eq!(MAZE_PACKS[0].layouts(), vec![Maze::classic()]);
+        for pack in &MAZE_PACKS {
+            assert!(!pack.mazes.is_empty(), "{}", pack.name);
+            for maze in pack.mazes {
+                assert!(parse_maze(maze.text).is_ok(), "{}", maze.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_mazes_cycle_by_level() {
+        assert_eq!(maze_index(1, 4), 0);
+        assert_eq!(maze_index(4, 4), 3);
+        assert_eq!(maze_index(5, 4), 0);
+        assert_eq!(maze_index(2, 1), 0);
+
+        let tour = &MAZE_PACKS[1];
+        assert_eq!(tour.maze_for_level(2).name, "Crossroads");
+        assert_eq!(tour.maze_for_level(6).name, "Crossroads");
+    }
+
+    #[test]
+    fn test_theme_wall_style() {
+        let theme = MazeTheme::default();
+        assert_eq!(
+            theme.wall_style(true),
+            "background: linear-gradient(45deg, #00c0ff, #00e2f9)"
+        );
+        assert_eq!(
+            theme.wall_style(false),
+            "background: linear-gradient(45deg, #00f, #7889e8)"
+        );
+    }
+}
@@ -1,4 +1,87 @@
 #[cfg(test)]
+pub mod brain_tests;
+#[cfg(test)]
+pub mod collision_tests;
+#[cfg(test)]
 pub mod component_tests;
 #[cfg(test)]
+pub mod engine_tests;
+#[cfg(test)]
 pub mod game_logic_tests;
+#[cfg(test)]
+pub mod ghost_house_tests;
+#[cfg(test)]
+pub mod ghost_mode_tests;
+#[cfg(test)]
+pub mod maze_tests;
+#[cfg(test)]
+pub mod pathfinding_tests;
+#[cfg(test)]
+pub mod replay_tests;
+
+#[cfg(test)]
+use crate::brain::{GhostBrains, GhostView};
+#[cfg(test)]
+use crate::engine::GameRng;
+#[cfg(test)]
+use crate::game_logic::move_ghosts;
+#[cfg(test)]
+use crate::maze::{Maze, Tile};
+#[cfg(test)]
+use crate::models::{Direction, Ghost, Position};
+#[cfg(test)]
+use crate::pathfinding::DistanceTable;
+
+/// Builds a maze from ASCII rows without the checks `parse_maze` makes, for
+/// tests that only need a patch of corridor. `P` marks Pac-Man's spawn,
+/// otherwise everyone spawns in the top-left corner.
+#[cfg(test)]
+pub fn maze_from(rows: &[&str]) -> Maze {
+    let mut spawn = Position { x: 0, y: 0 };
+    let tiles = rows
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .map(|(x, symbol)| match symbol {
+                    'P' => {
+                        spawn = Position { x, y };
+                        Tile::Empty
+                    }
+                    _ => Tile::from_symbol(symbol).expect("test maze tile"),
+                })
+                .collect()
+        })
+        .collect();
+    Maze {
+        tiles,
+        pacman_spawn: spawn.clone(),
+        ghost_spawns: vec![],
+        house_exit: spawn.clone(),
+        fruit_spawn: spawn,
+    }
+}
+
+/// Moves `ghosts` one tick with their built-in brains, viewing the board the
+/// way the engine would with Pac-Man on `pacman_pos`.
+#[cfg(test)]
+pub fn step_ghosts(
+    ghosts: &mut [Ghost],
+    pacman_pos: &Position,
+    pacman_direction: Direction,
+    distances: Option<&DistanceTable>,
+    maze: &Maze,
+    speeds: &[u32],
+    rng: &mut GameRng,
+) {
+    let snapshot = ghosts.to_vec();
+    let view = GhostView {
+        maze,
+        pacman_pos,
+        pacman_direction,
+        ghosts: &snapshot,
+        distances,
+    };
+    move_ghosts(ghosts, &view, &GhostBrains::default(), speeds, rng);
+}
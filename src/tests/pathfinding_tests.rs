@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::game::SUBTILES_PER_TILE;
+    use crate::engine::GameRng;
+    use crate::game_logic::{find_ghost_move, find_shortest_path_move};
+    use crate::maze::{Maze, Walker};
+    use crate::models::{Direction, Ghost, GhostId, GhostMode, Position};
+    use crate::pathfinding::{a_star, bfs, DistanceTable};
+    use crate::tests::{maze_from, step_ghosts};
+
+    /// The ghost at (2, 3) is right next to its target at (5, 3) as the crow
+    /// flies, but the wall between them only opens at the far end of the top
+    /// corridor, and the tile that looks closer is a dead end.
+    fn create_detour_maze() -> Maze {
+        maze_from(&[
+            "#########",
+            "#.......#",
+            "#.#####.#",
+            "#...#...#",
+            "#########",
+        ])
+    }
+
+    fn create_chaser(position: Position) -> Ghost {
+        let mut ghost = Ghost::new(GhostId::Blinky, position, Position { x: 0, y: 0 });
+        ghost.mode = GhostMode::Chase;
+        ghost
+    }
+
+    #[test]
+    fn test_bfs_counts_steps_to_every_reachable_tile() {
+        let maze = create_detour_maze();
+        let steps = bfs(&maze, &Position { x: 2, y: 3 }, Walker::Ghost);
+
+        assert_eq!(steps[3][2], Some(0));
+        assert_eq!(steps[3][3], Some(1));
+        assert_eq!(steps[1][1], Some(3));
+        assert_eq!(steps[3][5], Some(13));
+        assert_eq!(steps[3][4], None);
+    }
+
+    #[test]
+    fn test_a_star_finds_shortest_path() {
+        let maze = create_detour_maze();
+        let from = Position { x: 2, y: 3 };
+        let to = Position { x: 5, y: 3 };
+        let path = a_star(&maze, &from, &to, Walker::Ghost).unwrap();
+
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&Position { x: 1, y: 3 }));
+        assert_eq!(path.last(), Some(&to));
+        assert_eq!(a_star(&maze, &from, &from, Walker::Ghost), Some(vec![]));
+        assert_eq!(
+            a_star(&maze, &from, &Position { x: 4, y: 3 }, Walker::Ghost),
+            None
+        );
+    }
+
+    #[test]
+    fn test_a_star_takes_the_tunnel() {
+        let maze = maze_from(&["#######", "T.. ..T", "#######"]);
+        let path = a_star(
+            &maze,
+            &Position { x: 1, y: 1 },
+            &Position { x: 5, y: 1 },
+            Walker::Ghost,
+        )
+        .unwrap();
+
+        assert_eq!(
+            path,
+            vec![
+                Position { x: 0, y: 1 },
+                Position { x: 6, y: 1 },
+                Position { x: 5, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_a_star_agrees_with_bfs_on_classic_maze() {
+        let maze = Maze::classic();
+        let from = maze.pacman_spawn.clone();
+        let steps = bfs(&maze, &from, Walker::Ghost);
+
+        for (y, row) in steps.iter().enumerate() {
+            for (x, distance) in row.iter().enumerate() {
+                let to = Position { x, y };
+                let path = a_star(&maze, &from, &to, Walker::Ghost);
+                assert_eq!(path.map(|path| path.len() as u32), *distance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_table_matches_bfs() {
+        let maze = Maze::classic();
+        let table = DistanceTable::new(&maze, Walker::Ghost);
+
+        for start in [maze.pacman_spawn.clone(), maze.house_exit.clone()] {
+            let steps = bfs(&maze, &start, Walker::Ghost);
+            for (y, row) in steps.iter().enumerate() {
+                for (x, distance) in row.iter().enumerate() {
+                    let to = Position { x, y };
+                    let expected = distance.filter(|_| maze.is_walkable_for(&to, Walker::Ghost));
+                    assert_eq!(table.distance(&start, &to), expected);
+                }
+            }
+        }
+        assert_eq!(
+            table.distance(&maze.pacman_spawn, &Position { x: 0, y: 0 }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_greedy_ghost_is_lured_into_dead_end() {
+        let maze = create_detour_maze();
+        let table = DistanceTable::new(&maze, Walker::Ghost);
+        let ghost = create_chaser(Position { x: 2, y: 3 });
+        let target = Position { x: 5, y: 3 };
+
+        assert_eq!(
+            find_ghost_move(&ghost, &target, &maze),
+            Some(Position { x: 3, y: 3 })
+        );
+        assert_eq!(
+            find_shortest_path_move(&ghost, &target, &maze, &table),
+            Some(Position { x: 1, y: 3 })
+        );
+        assert_eq!(
+            find_shortest_path_move(&ghost, &Position { x: 4, y: 3 }, &maze, &table),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pathfinding_ghost_catches_pacman_around_the_wall() {
+        let maze = create_detour_maze();
+        let table = DistanceTable::new(&maze, Walker::Ghost);
+        let pacman_pos = Position { x: 5, y: 3 };
+        let mut ghosts = vec![create_chaser(Position { x: 2, y: 3 })];
+        let mut rng = GameRng::new(1);
+
+        for _ in 0..13 {
+            step_ghosts(
+                &mut ghosts,
+                &pacman_pos,
+                Direction::None,
+                Some(&table),
+                &maze,
+                &[SUBTILES_PER_TILE],
+                &mut rng,
+            );
+        }
+        assert_eq!(ghosts[0].position, pacman_pos);
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod tests {
+    use crate::brain::GhostBrains;
+    use crate::engine::{GameState, Input, Replay, ReplayPlayer};
+    use crate::maze::{parse_maze, Maze, MazePack, MAZE_PACKS};
+    use crate::models::{Direction, Ghost};
+    use crate::pathfinding::GhostAi;
+
+    fn record_game(seed: u64, ticks: u32) -> (Replay, GameState) {
+        let moves = [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ];
+        let mut state = GameState::new(seed);
+        state.start();
+        let mut replay = Replay::new(seed);
+
+        for tick in 0..ticks {
+            let direction = (tick % 7 == 0).then(|| moves[(tick / 7) as usize % moves.len()]);
+            let input = Input { direction };
+            replay.record(input);
+            state.step(input);
+        }
+        (replay, state)
+    }
+
+    fn play(replay: &Replay) -> GameState {
+        let mut state = replay.initial_state();
+        let mut player = ReplayPlayer::new(replay.clone());
+        while let Some(input) = player.next_input() {
+            state.step(input);
+        }
+        state
+    }
+
+    /// Every ghost after each tick of `replay`.
+    fn play_trail(replay: &Replay) -> Vec<Vec<Ghost>> {
+        let mut state = replay.initial_state();
+        let mut player = ReplayPlayer::new(replay.clone());
+        let mut trail = Vec::new();
+        while let Some(input) = player.next_input() {
+            state.step(input);
+            trail.push(state.ghosts.clone());
+        }
+        trail
+    }
+
+    #[test]
+    fn test_record_stores_only_direction_changes() {
+        let mut replay = Replay::new(3);
+        replay.record(Input::default());
+        replay.record(Input {
+            direction: Some(Direction::Left),
+        });
+        replay.record(Input::default());
+
+        assert_eq!(replay.length, 3);
+        assert_eq!(replay.inputs, vec![(1, Direction::Left)]);
+    }
+
+    #[test]
+    fn test_player_feeds_inputs_back_in_order() {
+        let mut replay = Replay::new(3);
+        replay.record(Input::default());
+        replay.record(Input {
+            direction: Some(Direction::Left),
+        });
+
+        let mut player = ReplayPlayer::new(replay);
+        assert_eq!(player.next_input(), Some(Input::default()));
+        assert_eq!(
+            player.next_input(),
+            Some(Input {
+                direction: Some(Direction::Left)
+            })
+        );
+        assert_eq!(player.next_input(), None);
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn test_replay_reproduces_game_frame_for_frame() {
+        let (replay, original) = record_game(2024, 400);
+        let replayed = play(&replay);
+
+        assert!(replayed == original);
+        assert_eq!(replayed.score, original.score);
+        assert_eq!(replayed.ghosts, original.ghosts);
+    }
+
+    #[test]
+    fn test_replay_json_round_trip() {
+        let (replay, _) = record_game(77, 50);
+        let json = replay.to_json();
+
+        assert_eq!(Replay::from_json(&json), Ok(replay));
+    }
+
+    #[test]
+    fn test_replay_rejects_unknown_version() {
+        let mut replay = Replay::new(1);
+        replay.version = 99;
+
+        assert!(Replay::from_json(&replay.to_json()).is_err());
+        assert!(Replay::from_json("not json").is_err());
+    }
+
+    /// A three-tick replay with `inputs` spliced in as if hand-edited.
+    fn edited_replay(inputs: Vec<(u32, Direction)>) -> String {
+        let mut replay = Replay::new(1);
+        replay.length = 3;
+        replay.inputs = inputs;
+        replay.to_json()
+    }
+
+    #[test]
+    fn test_replay_with_inputs_out_of_order_is_rejected() {
+        let json = edited_replay(vec![(2, Direction::Left), (1, Direction::Up)]);
+        assert_eq!(
+            Replay::from_json(&json).unwrap_err().to_string(),
+            "inputs out of order at tick 1"
+        );
+    }
+
+    #[test]
+    fn test_replay_with_repeated_input_tick_is_rejected() {
+        let json = edited_replay(vec![(1, Direction::Left), (1, Direction::Up)]);
+        assert_eq!(
+            Replay::from_json(&json).unwrap_err().to_string(),
+            "inputs out of order at tick 1"
+        );
+    }
+
+    #[test]
+    fn test_replay_with_input_after_length_is_rejected() {
+        let json = edited_replay(vec![(0, Direction::Left), (3, Direction::Up)]);
+        assert_eq!(
+            Replay::from_json(&json).unwrap_err().to_string(),
+            "input at tick 3 after replay length"
+        );
+        assert!(Replay::from_json(&edited_replay(vec![(2, Direction::Up)])).is_ok());
+    }
+
+    #[test]
+    fn test_custom_maze_is_recorded_with_replay() {
+        let text = Maze::classic().to_string().replacen('.', " ", 1);
+        let mut state = GameState::from_maze(parse_maze(&text).unwrap(), 9);
+        state.start();
+        let replay = Replay::for_game(&state);
+        assert_eq!(replay.mazes, vec![text]);
+
+        let loaded = Replay::from_json(&replay.to_json()).unwrap();
+        assert_eq!(loaded.initial_state().maze, state.maze);
+        assert!(Replay::for_game(&GameState::new(9)).mazes.is_empty());
+    }
+
+    #[test]
+    fn test_replay_with_broken_maze_is_rejected() {
+        let mut replay = Replay::new(1);
+        replay.mazes = vec![Maze::classic().to_string(), "###\n".to_string()];
+        assert!(Replay::from_json(&replay.to_json()).is_err());
+    }
+
+    #[test]
+    fn test_maze_pack_is_recorded_with_replay() {
+        let pack = &MAZE_PACKS[1];
+        let mut state = GameState::from_mazes(pack.layouts(), 4);
+        state.start();
+        let replay = Replay::for_game(&state);
+        assert_eq!(replay.mazes.len(), pack.mazes.len());
+
+        let loaded = Replay::from_json(&replay.to_json()).unwrap();
+        assert_eq!(loaded.initial_state().layouts(), state.layouts());
+        assert_eq!(MazePack::find(loaded.initial_state().layouts()), Some(1));
+    }
+
+    #[test]
+    fn test_ghost_ai_is_recorded_with_replay() {
+        let mut state = GameState::new(5);
+        state.ghost_ai = GhostAi::ShortestPath;
+        state.start();
+        let mut replay = Replay::for_game(&state);
+        for _ in 0..80 {
+            replay.record(Input::default());
+            state.step(Input::default());
+        }
+
+        let loaded = Replay::from_json(&replay.to_json()).unwrap();
+        assert_eq!(loaded.ghost_ai, GhostAi::ShortestPath);
+        let replayed = play(&loaded);
+        assert_eq!(replayed.ghosts, state.ghosts);
+        assert_eq!(replayed.ghost_ai, GhostAi::ShortestPath);
+    }
+
+    #[test]
+    fn test_ghost_brains_are_recorded_with_replay() {
+        let names: Vec<String> = ["pokey", "shadow", "shadow", "speedy"]
+            .map(String::from)
+            .to_vec();
+        let mut state = GameState::new(5);
+        state.brains = GhostBrains::from_names(&names);
+        state.start();
+        let mut replay = Replay::for_game(&state);
+        let mut trail = Vec::new();
+        for _ in 0..300 {
+            replay.record(Input::default());
+            state.step(Input::default());
+            trail.push(state.ghosts.clone());
+        }
+
+        let loaded = Replay::from_json(&replay.to_json()).unwrap();
+        assert_eq!(loaded.brains, names);
+        assert_eq!(loaded.initial_state().brains.names(), names);
+        assert_eq!(play_trail(&loaded), trail);
+
+        let arcade = Replay {
+            brains: Vec::new(),
+            ..loaded
+        };
+        assert_ne!(play_trail(&arcade), trail);
+    }
+}